@@ -0,0 +1,167 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Attribute, Data, DeriveInput, Error, Fields, GenericParam, LitStr, parse_macro_input};
+
+/// Derives `aoc_rust::parsers::PuzzleParse` for a struct with named fields.
+/// The fields are read in order, separated by the struct's `separator`, a
+/// space by default. Field attributes describe how each field is written:
+///
+/// - `delimiters = "()"`: the field is a group between these characters.
+/// - `separator = ","`: the field is a list of values with this separator.
+/// - `repeated`: the field is a list of delimited groups, one per value.
+///
+/// Every error names the field being read.
+#[proc_macro_derive(PuzzleParse, attributes(puzzle))]
+pub fn derive_puzzle_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Options {
+    separator: Option<String>,
+    delimiters: Option<(char, char)>,
+    repeated: bool,
+}
+
+fn options(attrs: &[Attribute]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("puzzle")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("separator") {
+                let separator: LitStr = meta.value()?.parse()?;
+                if separator.value().is_empty() {
+                    return Err(Error::new(separator.span(), "the separator is empty"));
+                }
+                options.separator = Some(separator.value());
+            } else if meta.path.is_ident("delimiters") {
+                let delimiters: LitStr = meta.value()?.parse()?;
+                let chars: Vec<char> = delimiters.value().chars().collect();
+                let [open, close] = chars[..] else {
+                    let message = "expected an opening and a closing character, like \"()\"";
+                    return Err(Error::new(delimiters.span(), message));
+                };
+                options.delimiters = Some((open, close));
+            } else if meta.path.is_ident("repeated") {
+                options.repeated = true;
+            } else {
+                return Err(meta.error("expected `separator`, `delimiters` or `repeated`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let params: Vec<_> = input.generics.params.iter().collect();
+    let (lifetime, generics) = match params[..] {
+        [] => (quote!('a), quote!()),
+        [GenericParam::Lifetime(param)] => {
+            let lifetime = &param.lifetime;
+            (quote!(#lifetime), quote!(<#lifetime>))
+        }
+        _ => {
+            let message = "PuzzleParse only supports a lifetime of the parsed text";
+            return Err(Error::new(input.generics.span(), message));
+        }
+    };
+    let Data::Struct(data) = &input.data else {
+        let message = "PuzzleParse can only be derived for structs";
+        return Err(Error::new(name.span(), message));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        let message = "PuzzleParse needs a struct with named fields";
+        return Err(Error::new(data.fields.span(), message));
+    };
+    let struct_options = options(&input.attrs)?;
+    if struct_options.delimiters.is_some() || struct_options.repeated {
+        let message = "only `separator` applies to the whole struct";
+        return Err(Error::new(name.span(), message));
+    }
+    let separator = struct_options.separator.unwrap_or_else(|| " ".to_string());
+
+    // Each field is bound to a local of its own name, so the locals of the
+    // generated parser are prefixed to keep fields from shadowing them.
+    let count = fields.named.len();
+    let mut reads = Vec::new();
+    for (i, field) in fields.named.iter().enumerate() {
+        let ident = field.ident.as_ref().unwrap();
+        let label = ident.to_string();
+        let options = options(&field.attrs)?;
+        let read = match &options.separator {
+            Some(separator) => quote!(derive::list(__aoc_input, __aoc_text, #separator)),
+            None => quote!(::aoc_rust::parsers::PuzzleParse::read(
+                __aoc_input,
+                __aoc_text
+            )),
+        };
+        let extent = match (options.delimiters, options.repeated) {
+            (Some((open, close)), true) => {
+                let first = i == 0;
+                quote! {
+                    let (#ident, __aoc_rest) = derive::in_field(
+                        derive::groups(__aoc_input, __aoc_rest, #open, #close, #separator, #first),
+                        #label,
+                    )?;
+                    let #ident = derive::in_field(
+                        #ident
+                            .into_iter()
+                            .map(|__aoc_text| #read)
+                            .collect::<Result<_, _>>(),
+                        #label,
+                    )?;
+                }
+            }
+            (Some((open, close)), false) => quote! {
+                let (__aoc_text, __aoc_rest) = derive::in_field(
+                    ::aoc_rust::parsers::delimited_group(__aoc_input, __aoc_rest, #open, #close),
+                    #label,
+                )?;
+                let #ident = derive::in_field(#read, #label)?;
+            },
+            (None, true) => {
+                let message = "`repeated` needs `delimiters` around each value";
+                return Err(Error::new(field.span(), message));
+            }
+            (None, false) => {
+                let end = if i + 1 == count {
+                    quote!(None)
+                } else {
+                    quote!(Some(#separator))
+                };
+                quote! {
+                    let (__aoc_text, __aoc_rest) = derive::until(__aoc_rest, #end);
+                    let #ident = derive::in_field(#read, #label)?;
+                }
+            }
+        };
+        if i > 0 && !options.repeated {
+            reads.push(quote! {
+                let __aoc_rest = derive::in_field(derive::separator(__aoc_input, __aoc_rest, #separator), #label)?;
+            });
+        }
+        reads.push(extent);
+    }
+    let idents = fields.named.iter().map(|field| &field.ident);
+
+    Ok(quote! {
+        impl<#lifetime> ::aoc_rust::parsers::PuzzleParse<#lifetime> for #name #generics {
+            fn read(
+                __aoc_input: &str,
+                __aoc_text: &#lifetime str,
+            ) -> Result<Self, ::aoc_rust::parsers::ParseError> {
+                use ::aoc_rust::parsers::derive;
+                let __aoc_rest = __aoc_text;
+                #(#reads)*
+                derive::end(__aoc_input, __aoc_rest)?;
+                Ok(#name { #(#idents),* })
+            }
+        }
+    })
+}
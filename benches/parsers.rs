@@ -0,0 +1,129 @@
+//! Compares the byte cursor of `parsers` with the `str` methods the days use.
+//! `cargo bench` times every case; as a test it runs each of them once.
+
+use aoc_rust::parsers::Cursor;
+use aoc_rust::random::Rng;
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Parses a whole input and sums what it read, so that both approaches can
+/// be checked against each other.
+type Parse<'a> = &'a dyn Fn() -> u64;
+
+fn main() {
+    let timed = env::args().any(|arg| arg == "--bench");
+    let mut rng = Rng::new(2025);
+    let ranges = (0..100_000)
+        .map(|_| {
+            let low = rng.range(1..=10u64.pow(12));
+            format!("{low}-{}", low + rng.range(0..=10u64.pow(6)))
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    let rotations: String = (0..200_000)
+        .map(|_| format!("{}\n", rng.range(0..=1998i64) - 999))
+        .collect();
+    let banks: String = (0..10_000)
+        .map(|_| {
+            let digits: String = (0..100).map(|_| rng.range(b'1'..=b'9') as char).collect();
+            digits + "\n"
+        })
+        .collect();
+
+    let cases: [(&str, Parse, Parse); 4] = [
+        (
+            "unsigned ranges",
+            &|| {
+                ranges
+                    .split(',')
+                    .map(|range| range.split_once('-').unwrap())
+                    .map(|(low, high)| low.parse::<u64>().unwrap() ^ high.parse::<u64>().unwrap())
+                    .sum()
+            },
+            &|| {
+                let mut cursor = Cursor::new(&ranges);
+                let mut sum = 0;
+                while !cursor.is_empty() {
+                    let low = cursor.unsigned().unwrap();
+                    cursor.expect(b'-').unwrap();
+                    sum += low ^ cursor.unsigned().unwrap();
+                    cursor.skip(b',');
+                }
+                sum
+            },
+        ),
+        (
+            "signed lines",
+            &|| {
+                rotations
+                    .lines()
+                    .map(|line| line.parse::<i64>().unwrap() as u64)
+                    .fold(0, u64::wrapping_add)
+            },
+            &|| {
+                let mut cursor = Cursor::new(&rotations);
+                let mut sum = 0u64;
+                while !cursor.is_empty() {
+                    sum = sum.wrapping_add(cursor.signed().unwrap() as u64);
+                    cursor.skip_all(b"\r\n");
+                }
+                sum
+            },
+        ),
+        (
+            "lines",
+            &|| banks.lines().map(|line| line.len() as u64).sum(),
+            &|| {
+                let mut cursor = Cursor::new(&banks);
+                std::iter::from_fn(|| cursor.line())
+                    .map(|line| line.len() as u64)
+                    .sum()
+            },
+        ),
+        (
+            "digit rows",
+            &|| {
+                banks
+                    .lines()
+                    .map(|line| {
+                        line.chars()
+                            .map(|c| c.to_digit(10).unwrap())
+                            .collect::<Vec<_>>()
+                    })
+                    .map(|digits| digits.iter().map(|&d| d as u64).sum::<u64>())
+                    .sum()
+            },
+            &|| {
+                let mut cursor = Cursor::new(&banks);
+                let mut sum = 0;
+                while !cursor.is_empty() {
+                    let digits = cursor.digit_row().unwrap();
+                    sum += digits.iter().map(|&d| d as u64).sum::<u64>();
+                }
+                sum
+            },
+        ),
+    ];
+
+    for (name, strings, cursor) in cases {
+        assert_eq!(strings(), cursor(), "{name}: both approaches should agree");
+        if timed {
+            let (strings, cursor) = (time(strings), time(cursor));
+            let speedup = strings.as_secs_f64() / cursor.as_secs_f64();
+            println!("{name:<16} str {strings:>10.2?}   cursor {cursor:>10.2?}   {speedup:.1}x");
+        }
+    }
+}
+
+/// The best time of several runs of `f`.
+fn time(f: Parse) -> Duration {
+    (0..20)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
@@ -0,0 +1,164 @@
+use crate::runner::Params;
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const CACHE_FILE: &str = "answers-cache.tsv";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Key {
+    year: i32,
+    day: i32,
+    part: i32,
+    input: u64,
+    params: String,
+}
+
+/// Answers of earlier runs, keyed by the input and the build that computed
+/// them. Any change to the code gives a new build, which invalidates every
+/// cached answer.
+pub struct Cache {
+    path: PathBuf,
+    build: String,
+    answers: HashMap<Key, String>,
+}
+
+impl Cache {
+    /// The answers cached for `build`. Answers of other builds are dropped
+    /// from the file on the next save.
+    pub fn load(path: &Path, build: &str) -> Cache {
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let answers = content
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split('\t').collect();
+                let [line_build, year, day, part, input, params, answer] = fields.as_slice() else {
+                    return None;
+                };
+                if *line_build != build {
+                    return None;
+                }
+                let key = Key {
+                    year: year.parse().ok()?,
+                    day: day.parse().ok()?,
+                    part: part.parse().ok()?,
+                    input: u64::from_str_radix(input, 16).ok()?,
+                    params: params.to_string(),
+                };
+                Some((key, answer.to_string()))
+            })
+            .collect();
+        Cache {
+            path: path.to_path_buf(),
+            build: build.to_string(),
+            answers,
+        }
+    }
+
+    pub fn get(
+        &self,
+        year: i32,
+        day: i32,
+        part: i32,
+        input: &str,
+        params: &Params,
+    ) -> Option<&str> {
+        let key = key(year, day, part, input, params);
+        self.answers.get(&key).map(String::as_str)
+    }
+
+    /// Adds the answer and saves the cache right away, so that the answers of
+    /// slow days survive a later day crashing.
+    pub fn insert(
+        &mut self,
+        year: i32,
+        day: i32,
+        part: i32,
+        input: &str,
+        params: &Params,
+        answer: &str,
+    ) -> io::Result<()> {
+        let key = key(year, day, part, input, params);
+        self.answers.insert(key, answer.to_string());
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let lines: String = self
+            .answers
+            .iter()
+            .sorted_by_key(|(key, _)| (key.year, key.day, key.part, key.input))
+            .map(|(key, answer)| {
+                format!(
+                    "{}\t{}\t{}\t{}\t{:016x}\t{}\t{answer}\n",
+                    self.build, key.year, key.day, key.part, key.input, key.params
+                )
+            })
+            .collect();
+        fs::write(&self.path, lines)
+    }
+}
+
+fn key(year: i32, day: i32, part: i32, input: &str, params: &Params) -> Key {
+    let params = params
+        .iter()
+        .sorted()
+        .map(|(name, value)| format!("{name}={value}"))
+        .join(",");
+    Key {
+        year,
+        day,
+        part,
+        input: hash(input.as_bytes()),
+        params,
+    }
+}
+
+/// Identifies the running build by hashing its executable. `None` when the
+/// executable cannot be read, in which case nothing should be cached.
+pub fn build_id() -> Option<String> {
+    let executable = fs::read(env::current_exe().ok()?).ok()?;
+    Some(format!("{:016x}", hash(&executable)))
+}
+
+/// 64-bit FNV-1a, which is stable across Rust versions unlike the standard
+/// library hasher.
+pub fn hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    pub fn answers_are_keyed_by_build_input_and_params() {
+        let path = env::temp_dir().join(format!("aoc-cache-test-{}.tsv", process::id()));
+        let mut params = Params::default();
+        let mut cache = Cache::load(&path, "build1");
+        cache.insert(2025, 8, 1, "1,2,3", &params, "40").unwrap();
+
+        let cache = Cache::load(&path, "build1");
+        assert_eq!(cache.get(2025, 8, 1, "1,2,3", &params), Some("40"));
+        assert_eq!(cache.get(2025, 8, 2, "1,2,3", &params), None);
+        assert_eq!(cache.get(2025, 8, 1, "1,2,4", &params), None);
+        params.set("connections", "10");
+        assert_eq!(cache.get(2025, 8, 1, "1,2,3", &params), None);
+
+        let cache = Cache::load(&path, "build2");
+        assert_eq!(cache.get(2025, 8, 1, "1,2,3", &Params::default()), None);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    pub fn fnv() {
+        assert_eq!(hash(b""), 0xcbf29ce484222325);
+        assert_eq!(hash(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
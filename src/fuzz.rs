@@ -0,0 +1,112 @@
+use crate::random::Rng;
+use crate::runner::{Solution, catch};
+use std::fs;
+
+/// Characters mutations insert: the puzzle syntax, whitespace, signs, and
+/// multi-byte characters that break parsers slicing strings by byte.
+const CHARACTERS: &[char] = &[
+    '0', '1', '9', '-', '+', '*', ',', ':', 'x', '#', '.', '@', '^', 'S', 'L', 'R', '(', ')', '[',
+    ']', '{', '}', ' ', '\n', '\t', 'é', '€', '🎄',
+];
+
+/// A parse that panicked instead of returning an error.
+#[derive(Debug)]
+pub struct Crash {
+    pub input: String,
+    pub message: String,
+}
+
+/// Inputs to start mutating from: the example if it is on disk, and a small
+/// generated input.
+pub fn seeds(solution: &Solution, rng: &mut Rng) -> Vec<String> {
+    let mut seeds = Vec::new();
+    let example = format!("input/{}/test{}.txt", solution.year, solution.day);
+    if let Ok(example) = fs::read_to_string(example) {
+        seeds.push(example);
+    }
+    seeds.push((solution.generate)(rng, 3));
+    seeds
+}
+
+/// Applies a few random edits to the input, keeping it valid UTF-8.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1..=3) {
+        let position = rng.range(0..=chars.len());
+        match rng.range(0..=5) {
+            0 if position < chars.len() => {
+                chars.remove(position);
+            }
+            1 => chars.insert(position, *rng.choose(CHARACTERS)),
+            2 if position < chars.len() => chars[position] = *rng.choose(CHARACTERS),
+            3 => chars.truncate(position),
+            4 => {
+                let start = rng.range(0..=position);
+                let copy = chars[start..position].to_vec();
+                chars.splice(position..position, copy);
+            }
+            5 => {
+                chars.splice(position..position, "99999999999999999999".chars());
+            }
+            _ => {}
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Feeds mutated inputs to the parser and collects the panics, one per
+/// distinct panic message.
+pub fn fuzz(solution: &Solution, rng: &mut Rng, iterations: usize) -> Vec<Crash> {
+    let seeds = seeds(solution, rng);
+    let mut crashes: Vec<Crash> = Vec::new();
+    for _ in 0..iterations {
+        let seed = rng.choose(&seeds);
+        let input = mutate(rng, seed);
+        if let Err(message) = catch(|| (solution.parse)(&input))
+            && crashes.iter().all(|crash| crash.message != message)
+        {
+            crashes.push(Crash { input, message });
+        }
+    }
+    crashes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::solutions;
+
+    #[test]
+    pub fn parsers_do_not_panic() {
+        let mut rng = Rng::new(2025);
+        for solution in solutions() {
+            let crashes = fuzz(&solution, &mut rng, 500);
+            assert!(
+                crashes.is_empty(),
+                "{} day {} parser panicked: {crashes:?}",
+                solution.year,
+                solution.day
+            );
+        }
+    }
+
+    #[test]
+    pub fn finds_panics() {
+        let solution = Solution {
+            year: 0,
+            day: 0,
+            parse: |input| {
+                assert!(!input.contains('#'), "found a #");
+                Ok(input.to_string())
+            },
+            generate: |_, _| "a,b,c".to_string(),
+            visualize: None,
+            parts: Vec::new(),
+            examples: Vec::new(),
+        };
+        let crashes = fuzz(&solution, &mut Rng::new(1), 1000);
+        assert_eq!(crashes.len(), 1);
+        assert!(crashes[0].input.contains('#'));
+        assert_eq!(crashes[0].message, "found a #");
+    }
+}
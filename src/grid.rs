@@ -0,0 +1,675 @@
+use crate::parsers::{ParseError, char_at};
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::iter::{StepBy, Take};
+use std::ops::{Add, Index, IndexMut, Sub};
+use std::slice::{ChunksExact, ChunksExactMut, Iter, IterMut};
+
+/// A cell of a grid, counted from the top left corner with `y` going down.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Pos {
+        Pos { x, y }
+    }
+
+    /// The next position in a direction, `None` past the top or left edge.
+    pub fn step(self, direction: Direction) -> Option<Pos> {
+        let (dx, dy) = direction.offset();
+        self.offset(dx, dy)
+    }
+
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Pos> {
+        Some(Pos::new(
+            self.x.checked_add_signed(dx)?,
+            self.y.checked_add_signed(dy)?,
+        ))
+    }
+
+    pub fn manhattan(self, other: Pos) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, other: Pos) -> Pos {
+        Pos::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, other: Pos) -> Pos {
+        Pos::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// A position outside of a grid.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct OutOfBounds {
+    pub x: isize,
+    pub y: isize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Display for OutOfBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({}, {}) is outside of the {}x{} grid",
+            self.x, self.y, self.width, self.height
+        )
+    }
+}
+
+impl Error for OutOfBounds {}
+
+impl From<OutOfBounds> for String {
+    fn from(error: OutOfBounds) -> String {
+        error.to_string()
+    }
+}
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T: Copy> {
+    grid: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+/// Reads a grid of the characters of the input as they are.
+pub fn read_grid(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_with(input, Ok::<_, Infallible>)
+}
+
+impl<T: Copy> Grid<T> {
+    /// Reads a grid of one cell per character, converting each character with
+    /// `cell`. Its error describes what was expected instead, and every line
+    /// must be as long as the first one.
+    pub fn parse_with<E: Display>(
+        input: &str,
+        cell: impl Fn(char) -> Result<T, E>,
+    ) -> Result<Grid<T>, ParseError> {
+        let width = input
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::end(input, "a grid"))?
+            .chars()
+            .count();
+        let mut grid = Vec::with_capacity(input.len());
+        let mut height = 0;
+        for line in input.lines() {
+            for (i, c) in line.char_indices() {
+                let value = cell(c).map_err(|e| ParseError::at(input, char_at(line, i), e))?;
+                grid.push(value);
+            }
+            height += 1;
+            if grid.len() != width * height {
+                let expected = format!("a line of length {width}");
+                return Err(ParseError::at(input, line, expected));
+            }
+        }
+        Ok(Grid {
+            grid,
+            width,
+            height,
+        })
+    }
+
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        Grid {
+            grid: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    pub fn new_by_grid<U: Copy>(grid: &Grid<U>, value: T) -> Grid<T> {
+        Grid::new(grid.get_width(), grid.get_height(), value)
+    }
+
+    /// A grid of the given values, row by row.
+    pub fn from_vec(width: usize, height: usize, values: Vec<T>) -> Grid<T> {
+        assert_eq!(values.len(), width * height, "not a {width}x{height} grid");
+        Grid {
+            grid: values,
+            width,
+            height,
+        }
+    }
+
+    pub fn get_width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Result<T, OutOfBounds> {
+        Ok(self.grid[self.index_of(x as isize, y as isize)?])
+    }
+
+    /// The value at signed coordinates, `None` outside of the grid.
+    pub fn get_opt(&self, x: isize, y: isize) -> Option<T> {
+        Some(self.grid[self.index_of(x, y).ok()?])
+    }
+
+    /// Panics outside of the grid, like indexing.
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        self[Pos::new(x, y)] = value;
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    /// Every position of the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    fn index_of(&self, x: isize, y: isize) -> Result<usize, OutOfBounds> {
+        match (usize::try_from(x), usize::try_from(y)) {
+            (Ok(column), Ok(row)) if column < self.width && row < self.height => {
+                Ok(row * self.width + column)
+            }
+            _ => Err(OutOfBounds {
+                x,
+                y,
+                width: self.width,
+                height: self.height,
+            }),
+        }
+    }
+
+    pub fn map<U: Copy>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        let grid = self.iter().map(f).collect();
+        Grid {
+            grid,
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    pub fn neighbours(&self, x: usize, y: usize) -> Vec<T> {
+        self.neighbours8(x, y).map(|(_, _, value)| value).collect()
+    }
+
+    /// The cells above, right of, below and left of a cell, in that order,
+    /// with their coordinates.
+    pub fn neighbours4(&self, x: usize, y: usize) -> Neighbours<'_, T> {
+        Neighbours::new(self, x, y, &ORTHOGONAL)
+    }
+
+    /// The cells around a cell, clockwise from above.
+    pub fn neighbours8(&self, x: usize, y: usize) -> Neighbours<'_, T> {
+        Neighbours::new(self, x, y, &AROUND)
+    }
+
+    /// The cells touching a cell only at a corner, clockwise from the top
+    /// right.
+    pub fn diagonal_neighbours(&self, x: usize, y: usize) -> Neighbours<'_, T> {
+        Neighbours::new(self, x, y, &DIAGONAL)
+    }
+
+    pub fn iter(&'_ self) -> Iter<'_, T> {
+        self.grid.iter()
+    }
+
+    /// Every cell with its `(x, y)` coordinates, row by row.
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.grid
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| ((i % width, i / width), value))
+    }
+
+    pub fn enumerate_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.grid
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, value)| ((i % width, i / width), value))
+    }
+
+    /// The rows from top to bottom.
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        self.grid.chunks_exact(self.width.max(1))
+    }
+
+    pub fn rows_mut(&mut self) -> ChunksExactMut<'_, T> {
+        self.grid.chunks_exact_mut(self.width.max(1))
+    }
+
+    /// The columns from left to right, each from top to bottom.
+    pub fn columns(&self) -> impl Iterator<Item = StepBy<Iter<'_, T>>> {
+        // A grid without rows still has its columns, only they are empty.
+        let start = move |x: usize| x.min(self.grid.len());
+        (0..self.width).map(move |x| self.grid[start(x)..].iter().step_by(self.width))
+    }
+
+    /// One column from top to bottom. Unlike the rows, the columns share
+    /// their memory, so they can only be changed one at a time.
+    pub fn column_mut(&mut self, x: usize) -> StepBy<IterMut<'_, T>> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        let start = x.min(self.grid.len());
+        self.grid[start..].iter_mut().step_by(self.width)
+    }
+
+    /// The lines going down and to the right, starting from the bottom left
+    /// corner and ending in the top right one.
+    pub fn diagonals(&self) -> impl Iterator<Item = Take<StepBy<Iter<'_, T>>>> {
+        self.diagonals_by(self.width + 1, |d| {
+            let start = Pos::new(
+                d.saturating_sub(self.height - 1),
+                (self.height - 1).saturating_sub(d),
+            );
+            let length = (self.width - start.x).min(self.height - start.y);
+            (start, length)
+        })
+    }
+
+    /// The lines going down and to the left, starting from the top left
+    /// corner and ending in the bottom right one.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Take<StepBy<Iter<'_, T>>>> {
+        let last_x = self.width.saturating_sub(1);
+        self.diagonals_by(last_x.max(1), move |d| {
+            let start = Pos::new(d.min(last_x), d.saturating_sub(last_x));
+            let length = (start.x + 1).min(self.height - start.y);
+            (start, length)
+        })
+    }
+
+    /// The diagonal lines with cells `step` apart in the buffer, each given
+    /// by its first cell and its length. An empty grid has none, so `line`
+    /// is only called for a grid with at least one cell.
+    fn diagonals_by(
+        &self,
+        step: usize,
+        line: impl Fn(usize) -> (Pos, usize),
+    ) -> impl Iterator<Item = Take<StepBy<Iter<'_, T>>>> {
+        let count = if self.grid.is_empty() {
+            0
+        } else {
+            self.width + self.height - 1
+        };
+        (0..count).map(move |d| {
+            let (start, length) = line(d);
+            let start = start.y * self.width + start.x;
+            self.grid[start..].iter().step_by(step).take(length)
+        })
+    }
+
+    /// The grid mirrored along its main diagonal, so that rows become
+    /// columns.
+    pub fn transpose(&self) -> Grid<T> {
+        self.transformed(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// The grid turned a quarter clockwise.
+    pub fn rotate_90(&self) -> Grid<T> {
+        self.transformed(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    pub fn rotate_180(&self) -> Grid<T> {
+        self.transformed(self.width, self.height, |x, y| {
+            (self.width - 1 - x, self.height - 1 - y)
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise.
+    pub fn rotate_270(&self) -> Grid<T> {
+        self.transformed(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// The grid mirrored left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        self.transformed(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// The grid mirrored top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        self.transformed(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    /// The distinct grids among the 8 rotations and reflections of the grid,
+    /// starting with the grid itself.
+    pub fn symmetries(&self) -> Vec<Grid<T>>
+    where
+        T: PartialEq,
+    {
+        let rotations = [
+            self.clone(),
+            self.rotate_90(),
+            self.rotate_180(),
+            self.rotate_270(),
+        ];
+        let reflections = rotations.each_ref().map(Grid::flip_horizontal);
+        let mut symmetries: Vec<Grid<T>> = Vec::with_capacity(8);
+        for grid in rotations.into_iter().chain(reflections) {
+            if !symmetries.contains(&grid) {
+                symmetries.push(grid);
+            }
+        }
+        symmetries
+    }
+
+    /// A `width` by `height` grid taking each cell from the cell of this grid
+    /// at the coordinates `source` gives.
+    fn transformed(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Grid<T> {
+        let grid = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let (x, y) = source(x, y);
+                self.grid[y * self.width + x]
+            })
+            .collect();
+        Grid {
+            grid,
+            width,
+            height,
+        }
+    }
+
+    pub fn print_by<U: Copy + Display>(&self, f: impl Fn(&T) -> U) {
+        self.map(f).print();
+    }
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const DIAGONAL: [(isize, isize); 4] = [(1, -1), (1, 1), (-1, 1), (-1, -1)];
+const AROUND: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// The neighbours of a cell inside the grid as `(x, y, value)`.
+pub struct Neighbours<'a, T: Copy> {
+    grid: &'a Grid<T>,
+    x: usize,
+    y: usize,
+    offsets: Iter<'static, (isize, isize)>,
+}
+
+impl<'a, T: Copy> Neighbours<'a, T> {
+    fn new(
+        grid: &'a Grid<T>,
+        x: usize,
+        y: usize,
+        offsets: &'static [(isize, isize)],
+    ) -> Neighbours<'a, T> {
+        Neighbours {
+            grid,
+            x,
+            y,
+            offsets: offsets.iter(),
+        }
+    }
+}
+
+impl<T: Copy> Iterator for Neighbours<'_, T> {
+    type Item = (usize, usize, T);
+
+    fn next(&mut self) -> Option<(usize, usize, T)> {
+        self.offsets.find_map(|(dx, dy)| {
+            let x = self.x.checked_add_signed(*dx)?;
+            let y = self.y.checked_add_signed(*dy)?;
+            let value = self.grid.get(x, y).ok()?;
+            Some((x, y, value))
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+impl<T: Copy> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.index_of(pos.x as isize, pos.y as isize) {
+            Ok(index) => &self.grid[index],
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
+
+impl<T: Copy> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        match self.index_of(pos.x as isize, pos.y as isize) {
+            Ok(index) => &mut self.grid[index],
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
+
+impl<T: Copy + Debug> Debug for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.rows()).finish()
+    }
+}
+
+impl<T: Copy + Display> Grid<T> {
+    pub fn print(&self) {
+        print!("{self}");
+    }
+}
+
+impl<T: Copy + Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for value in row {
+                write!(f, "{value}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn neighbours() {
+        let grid = read_grid("abc\ndef\nghi\n").unwrap();
+        let around: Vec<_> = grid.neighbours8(1, 1).collect();
+        assert_eq!(
+            around,
+            vec![
+                (1, 0, 'b'),
+                (2, 0, 'c'),
+                (2, 1, 'f'),
+                (2, 2, 'i'),
+                (1, 2, 'h'),
+                (0, 2, 'g'),
+                (0, 1, 'd'),
+                (0, 0, 'a'),
+            ]
+        );
+        let corner: Vec<_> = grid.neighbours4(0, 0).collect();
+        assert_eq!(corner, vec![(1, 0, 'b'), (0, 1, 'd')]);
+        let corner: Vec<_> = grid.diagonal_neighbours(2, 0).collect();
+        assert_eq!(corner, vec![(1, 1, 'e')]);
+        assert_eq!(grid.neighbours(2, 2), vec!['f', 'h', 'e']);
+    }
+
+    #[test]
+    pub fn parse_with() {
+        let parse = |input| {
+            Grid::parse_with(input, |c| c.to_digit(10).ok_or("a digit")).map_err(String::from)
+        };
+        let grid = parse("123\n456\n").unwrap();
+        assert_eq!((grid.get_width(), grid.get_height()), (3, 2));
+        assert_eq!(grid[Pos::new(2, 1)], 6);
+        assert_eq!(
+            parse("123\n45\n789\n").unwrap_err().lines().next(),
+            Some("line 2, column 1: expected a line of length 3, found \"45\"")
+        );
+        assert_eq!(
+            parse("123\n4x6\n").unwrap_err().lines().next(),
+            Some("line 2, column 2: expected a digit, found \"x\"")
+        );
+        assert!(parse("").is_err());
+        assert_eq!(read_grid("ab\nc\n").unwrap_err().line, 2);
+    }
+
+    #[test]
+    pub fn lines() {
+        let mut grid = read_grid("abc\ndef\n").unwrap();
+        let rows: Vec<String> = grid.rows().map(|row| row.iter().collect()).collect();
+        assert_eq!(rows, vec!["abc", "def"]);
+        let columns: Vec<String> = grid.columns().map(|column| column.collect()).collect();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+        let diagonals: Vec<String> = grid.diagonals().map(|line| line.collect()).collect();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+        let diagonals: Vec<String> = grid.anti_diagonals().map(|line| line.collect()).collect();
+        assert_eq!(diagonals, vec!["a", "bd", "ce", "f"]);
+        let tall = read_grid("ab\ncd\nef\n").unwrap();
+        let diagonals: Vec<String> = tall.diagonals().map(|line| line.collect()).collect();
+        assert_eq!(diagonals, vec!["e", "cf", "ad", "b"]);
+        let diagonals: Vec<String> = tall.anti_diagonals().map(|line| line.collect()).collect();
+        assert_eq!(diagonals, vec!["a", "bc", "de", "f"]);
+
+        assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &'e')));
+        for ((x, y), value) in grid.enumerate_mut() {
+            if x == 2 || y == 0 {
+                *value = '#';
+            }
+        }
+        grid.rows_mut().last().unwrap()[0] = '.';
+        grid.column_mut(1)
+            .for_each(|value| *value = value.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "###\n.E#\n");
+    }
+
+    #[test]
+    pub fn empty_lines() {
+        for grid in [Grid::new(0, 3, 'a'), Grid::new(3, 0, 'a')] {
+            assert_eq!(grid.rows().count(), 0);
+            assert_eq!(grid.columns().flatten().count(), 0);
+            assert_eq!(grid.columns().count(), grid.get_width());
+            assert_eq!(grid.diagonals().count(), 0);
+            assert_eq!(grid.anti_diagonals().count(), 0);
+            assert_eq!(grid.enumerate().count(), 0);
+        }
+        let grid = Grid::parse_with("\n", Ok::<_, Infallible>).unwrap();
+        assert_eq!(grid.get_width(), 0);
+        assert_eq!(grid.anti_diagonals().count(), 0);
+    }
+
+    #[test]
+    pub fn symmetries() {
+        let grid = read_grid("abc\ndef\n").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_90().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_180().to_string(), "fed\ncba\n");
+        assert_eq!(grid.rotate_270().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_90().rotate_270(), grid);
+        assert_eq!(grid.flip_vertical(), grid.rotate_180().flip_horizontal());
+
+        assert_eq!(grid.symmetries().len(), 8);
+        assert!(grid.symmetries().contains(&grid.transpose()));
+        assert_eq!(read_grid("ab\nba\n").unwrap().symmetries().len(), 2);
+        assert_eq!(read_grid("aa\naa\n").unwrap().symmetries().len(), 1);
+        let corner = read_grid("#.\n##\n").unwrap().symmetries();
+        assert_eq!(corner.len(), 4);
+        assert_eq!(corner[0], read_grid("#.\n##\n").unwrap());
+    }
+
+    #[test]
+    pub fn positions() {
+        let mut grid = read_grid("abc\ndef\n").unwrap();
+        let pos = Pos::new(2, 0);
+        assert_eq!(grid[pos], 'c');
+        assert_eq!(pos.step(Direction::Down), Some(Pos::new(2, 1)));
+        assert_eq!(pos.step(Direction::Up), None);
+        assert_eq!(pos + Pos::new(1, 1) - Pos::new(3, 0), Pos::new(0, 1));
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Up.turn_left().opposite(), Direction::Right);
+
+        grid[Pos::new(0, 1)] = 'x';
+        assert_eq!(grid.get(0, 1), Ok('x'));
+        assert_eq!(grid.get_opt(-1, 0), None);
+        assert_eq!(grid.get_opt(1, 1), Some('e'));
+        assert_eq!(
+            grid.get(3, 0).unwrap_err().to_string(),
+            "(3, 0) is outside of the 3x2 grid"
+        );
+        assert!(!grid.contains(Pos::new(0, 2)));
+        let positions: Vec<_> = grid.positions().map(|pos| grid[pos]).collect();
+        assert_eq!(positions, vec!['a', 'b', 'c', 'x', 'e', 'f']);
+    }
+}
@@ -0,0 +1,223 @@
+use crate::runner::Timing;
+use itertools::Itertools;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const HISTORY_FILE: &str = "history.tsv";
+
+/// One timed run of a part implementation, stored as a tab separated line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub commit: String,
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    pub name: String,
+    pub time: Duration,
+}
+
+impl Record {
+    pub fn new(commit: &str, year: i32, day: i32, timing: &Timing) -> Record {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        Record {
+            timestamp: now.as_secs(),
+            commit: commit.to_string(),
+            year,
+            day,
+            part: timing.part,
+            name: timing.name.to_string(),
+            time: timing.time,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.commit,
+            self.year,
+            self.day,
+            self.part,
+            self.name,
+            self.time.as_nanos()
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Record, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [timestamp, commit, year, day, part, name, nanos] = fields.as_slice() else {
+            return Err(format!("History line {line:?} should have 7 fields"));
+        };
+        let number = |field: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid number {field:?} in history line {line:?}: {e}"))
+        };
+        Ok(Record {
+            timestamp: number(timestamp)?,
+            commit: commit.to_string(),
+            year: number(year)? as i32,
+            day: number(day)? as i32,
+            part: number(part)? as i32,
+            name: name.to_string(),
+            time: Duration::from_nanos(number(nanos)?),
+        })
+    }
+}
+
+/// The commit checked out in the repository at `root`, read straight from
+/// `.git` so that no git binary is needed. `None` outside a repository.
+pub fn git_commit(root: &Path) -> Option<String> {
+    let git = root.join(".git");
+    let head = fs::read_to_string(git.join("HEAD")).ok()?;
+    let Some(reference) = head.trim().strip_prefix("ref: ") else {
+        return Some(head.trim().to_string());
+    };
+    if let Ok(commit) = fs::read_to_string(git.join(reference)) {
+        return Some(commit.trim().to_string());
+    }
+    // Refs that have not changed in a while only live in packed-refs.
+    let packed = fs::read_to_string(git.join("packed-refs")).ok()?;
+    packed
+        .lines()
+        .filter_map(|line| line.split_once(' '))
+        .find(|(_, name)| *name == reference)
+        .map(|(commit, _)| commit.to_string())
+}
+
+pub fn append(path: &Path, records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for record in records {
+        writeln!(file, "{}", record.to_line())?;
+    }
+    Ok(())
+}
+
+/// All records in the history file, oldest first. A missing file is an empty
+/// history.
+pub fn load(path: &Path) -> Result<Vec<Record>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Cannot read {}: {e}", path.display())),
+    };
+    content
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(Record::from_line)
+        .collect()
+}
+
+/// The timings of one part implementation across commits. Each commit counts
+/// with its fastest run, which filters out most of the noise of a busy
+/// machine.
+#[derive(Debug)]
+pub struct Trend {
+    pub year: i32,
+    pub day: i32,
+    pub part: i32,
+    pub name: String,
+    /// Fastest time per commit, in the order the commits were first run.
+    pub commits: Vec<(String, Duration)>,
+}
+
+impl Trend {
+    pub fn best(&self) -> &(String, Duration) {
+        self.commits.iter().min_by_key(|(_, time)| *time).unwrap()
+    }
+
+    pub fn latest(&self) -> &(String, Duration) {
+        self.commits.last().unwrap()
+    }
+
+    /// How much slower the latest commit is than the best one, in percent.
+    pub fn slowdown(&self) -> f64 {
+        let best = self.best().1.as_secs_f64();
+        let latest = self.latest().1.as_secs_f64();
+        if best == 0.0 {
+            return 0.0;
+        }
+        (latest / best - 1.0) * 100.0
+    }
+
+    pub fn regressed(&self, threshold: f64) -> bool {
+        self.slowdown() > threshold
+    }
+}
+
+pub fn trends(records: &[Record]) -> Vec<Trend> {
+    let by_part = records
+        .iter()
+        .into_group_map_by(|r| (r.year, r.day, r.part, r.name.clone()));
+    by_part
+        .into_iter()
+        .map(|((year, day, part, name), records)| {
+            let commits = records
+                .iter()
+                .into_grouping_map_by(|r| r.commit.clone())
+                .min_by_key(|_, r| r.time);
+            let commits = records
+                .iter()
+                .map(|r| &r.commit)
+                .unique()
+                .map(|commit| (commit.clone(), commits[commit].time))
+                .collect();
+            Trend {
+                year,
+                day,
+                part,
+                name,
+                commits,
+            }
+        })
+        .sorted_by_key(|t| (t.year, t.day, t.part, t.name != "main", t.name.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(commit: &str, part: i32, millis: u64) -> Record {
+        Record {
+            timestamp: 0,
+            commit: commit.to_string(),
+            year: 2025,
+            day: 8,
+            part,
+            name: "main".to_string(),
+            time: Duration::from_millis(millis),
+        }
+    }
+
+    #[test]
+    pub fn round_trip() {
+        let record = record("abc", 1, 12);
+        assert_eq!(Record::from_line(&record.to_line()), Ok(record));
+        assert!(Record::from_line("1\tabc\t2025").is_err());
+    }
+
+    #[test]
+    pub fn detects_regressions() {
+        let records = vec![
+            record("a", 1, 100),
+            record("a", 2, 50),
+            record("b", 1, 80),
+            record("b", 1, 200),
+            record("b", 2, 52),
+            record("c", 1, 95),
+            record("c", 2, 80),
+        ];
+        let trends = trends(&records);
+        assert_eq!(trends.len(), 2);
+        let part1 = &trends[0];
+        assert_eq!(part1.best(), &("b".to_string(), Duration::from_millis(80)));
+        assert_eq!(part1.commits.len(), 3);
+        assert!(part1.regressed(10.0));
+        assert!(!part1.regressed(20.0));
+        assert!(trends[1].regressed(50.0));
+    }
+}
@@ -1,7 +1,24 @@
+// Lets `#[derive(PuzzleParse)]` refer to this crate as `aoc_rust` inside it too.
+extern crate self as aoc_rust;
+
+pub mod cache;
+pub mod fuzz;
+pub mod grid;
+pub mod history;
+pub mod parsers;
+pub mod property;
+pub mod random;
+pub mod report;
+pub mod repl;
+pub mod runner;
+pub mod status;
 pub mod year2025;
 
 pub mod input {
-    use std::fs;
+    use crate::parsers::ParseError;
+    use std::fs::{self, File};
+    use std::io::{BufRead, BufReader};
+    use std::path::Path;
 
     pub fn get_input(year: i32, day: i32) -> String {
         let path = format!("input/{year}/day{day}.txt");
@@ -12,119 +29,125 @@ pub mod input {
         let path = format!("input/{year}/test{day}.txt");
         fs::read_to_string(path).expect("No input file")
     }
-}
 
-pub mod parsers {
-    pub fn parse_with_delimiters<T>(start: char, end: char, input: &str, parser: impl Fn(&str) -> T) -> T {
-        assert_eq!(input.chars().nth(0).unwrap(), start);
-        assert_eq!(input.chars().nth_back(0).unwrap(), end);
-        parser(&input[1..input.len() - 1])
+    pub fn read_input(year: i32, day: i32) -> Result<String, String> {
+        read(&format!("input/{year}/day{day}.txt"))
     }
-}
-
-pub mod grid {
-    use std::fmt::Display;
-    use std::slice::Iter;
 
-    pub struct Grid<T: Copy> {
-        grid: Vec<T>,
-        width: usize,
-        height: usize,
+    pub fn read_test_input(year: i32, day: i32) -> Result<String, String> {
+        read(&format!("input/{year}/test{day}.txt"))
     }
 
-    pub fn read_grid(input: &str) -> Grid<char> {
-        let width = input.lines().next().unwrap().len();
-        let height = input.lines().count();
-        let grid: Vec<char> = input.lines().flat_map(|l| l.chars()).collect();
-        Grid {
-            grid,
-            width,
-            height,
-        }
+    /// Opens the input for reading a piece at a time with `stream`.
+    pub fn open_input(year: i32, day: i32) -> Result<BufReader<File>, String> {
+        open(&format!("input/{year}/day{day}.txt"))
     }
 
-    impl<T: Copy> Grid<T> {
-        pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
-            Grid {
-                grid: vec![value; width * height],
-                width,
-                height,
-            }
-        }
-
-        pub fn new_by_grid<U: Copy>(grid: &Grid<U>, value: T) -> Grid<T> {
-            Grid::new(grid.get_width(), grid.get_height(), value)
-        }
-
-        pub fn get_width(&self) -> usize {
-            self.width
-        }
-
-        pub fn get_height(&self) -> usize {
-            self.height
-        }
-
-        pub fn get(&self, x: usize, y: usize) -> Result<T, String> {
-            if x >= self.width || y >= self.height {
-                return Err(format!("Invalid coordinates ({x},{y})"));
-            }
-            Ok(self.grid[y * self.width + x])
-        }
+    pub fn open_test_input(year: i32, day: i32) -> Result<BufReader<File>, String> {
+        open(&format!("input/{year}/test{day}.txt"))
+    }
 
-        pub fn set(&mut self, x: usize, y: usize, value: T) {
-            if x >= self.width || y >= self.height {
-                panic!("Out of bounds");
+    /// Parses the records of `reader` one at a time, each ending with
+    /// `separator`, so that only the current record is held in memory. Each
+    /// record is parsed as an input of its own, and errors are moved back to
+    /// where the record starts in the whole input.
+    pub fn stream<T>(
+        reader: impl BufRead,
+        separator: u8,
+        parse: impl Fn(&str) -> Result<T, ParseError>,
+    ) -> impl Iterator<Item = Result<T, String>> {
+        let mut line = 1;
+        let mut column = 1;
+        reader.split(separator).map(move |record| {
+            let record = record.map_err(|e| format!("Cannot read line {line}: {e}"))?;
+            let record =
+                String::from_utf8(record).map_err(|e| format!("Cannot read line {line}: {e}"))?;
+            let record = match separator {
+                b'\n' => record.strip_suffix('\r').unwrap_or(&record),
+                _ => &record,
+            };
+            let parsed = parse(record).map_err(|error| {
+                // The error is located in the record, which starts at `line`
+                // and `column` of the file. Only the first line of the record
+                // starts mid-line, so only there the columns differ.
+                let file_line = line + error.line - 1;
+                let file_column = match error.line {
+                    1 => column + error.column - 1,
+                    _ => error.column,
+                };
+                // The snippet shows the record alone, so its caret keeps the
+                // column in the record.
+                let in_record = ParseError {
+                    line: file_line,
+                    ..error.clone()
+                };
+                let rendered = in_record.render();
+                let snippet = rendered.split_once('\n').map_or("", |(_, snippet)| snippet);
+                let in_file = ParseError {
+                    line: file_line,
+                    column: file_column,
+                    ..error
+                };
+                format!("{in_file}\n{snippet}")
+            });
+            match (separator, record.rsplit_once('\n')) {
+                (b'\n', _) => {
+                    line += 1;
+                    column = 1;
+                }
+                (_, Some((before, last))) => {
+                    line += before.matches('\n').count() + 1;
+                    column = last.chars().count() + 2;
+                }
+                (_, None) => column += record.chars().count() + 1,
             }
-            self.grid[y * self.width + x] = value;
-        }
+            parsed
+        })
+    }
 
-        pub fn map<U: Copy>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
-            let grid = self.iter().map(f).collect();
-            Grid {
-                grid,
-                width: self.width,
-                height: self.height,
-            }
-        }
+    pub fn has_input(year: i32, day: i32) -> bool {
+        Path::new(&format!("input/{year}/day{day}.txt")).exists()
+    }
 
-        pub fn neighbours(&self, x: usize, y: usize) -> Vec<T> {
-            let mut neighbours = Vec::new();
-            if x > 0 && y > 0 {
-                neighbours.push((x - 1, y - 1));
-            }
-            if x > 0 {
-                neighbours.push((x - 1, y));
-                neighbours.push((x - 1, y + 1));
-            }
-            if y > 0 {
-                neighbours.push((x, y - 1));
-                neighbours.push((x + 1, y - 1));
-            }
-            neighbours.push((x, y + 1));
-            neighbours.push((x + 1, y));
-            neighbours.push((x + 1, y + 1));
-            neighbours
-                .iter()
-                .filter_map(|(i, j)| self.get(*i, *j).ok())
-                .collect()
-        }
+    /// The accepted answers of a day, one line per part, as far as they
+    /// have been recorded.
+    pub fn read_answers(year: i32, day: i32) -> Vec<String> {
+        let answers = fs::read_to_string(format!("input/{year}/answers{day}.txt"));
+        answers
+            .map(|answers| answers.lines().map(|line| line.trim().to_string()).collect())
+            .unwrap_or_default()
+    }
 
-        pub fn iter(&'_ self) -> Iter<'_, T> {
-            self.grid.iter()
-        }
+    fn read(path: &str) -> Result<String, String> {
+        fs::read_to_string(path).map_err(|e| format!("Cannot read {path}: {e}"))
+    }
 
-        pub fn print_by<U: Copy + Display>(&self, f: impl Fn(&T) -> U) {
-            self.map(f).print();
-        }
+    fn open(path: &str) -> Result<BufReader<File>, String> {
+        let file = File::open(path).map_err(|e| format!("Cannot read {path}: {e}"))?;
+        Ok(BufReader::new(file))
     }
-    impl<T: Copy + Display> Grid<T> {
-        pub fn print(&self) {
-            for y in 0..self.height {
-                for x in 0..self.width {
-                    print!("{}", self.get(x, y).unwrap());
-                }
-                println!()
-            }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::parsers::parse_number;
+
+        #[test]
+        pub fn streamed_errors_are_located_in_the_file() {
+            let parse = |record: &str| parse_number::<u32>(record, record.trim(), "a number");
+            let error = |input: &str| {
+                stream(input.as_bytes(), b',', parse)
+                    .find_map(Result::err)
+                    .unwrap()
+            };
+            assert_eq!(
+                error("12,34,7x,89\n"),
+                "line 1, column 7: expected a number, found \"7x\"\n  |\n1 | 7x\n  | ^^"
+            );
+            assert_eq!(
+                error("12,\n34,7x,89\n"),
+                "line 2, column 4: expected a number, found \"7x\"\n  |\n2 | 7x\n  | ^^"
+            );
         }
     }
 }
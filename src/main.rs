@@ -1,9 +1,411 @@
+use aoc_rust::cache::{CACHE_FILE, Cache, build_id};
+use aoc_rust::fuzz;
+use aoc_rust::history::{self, HISTORY_FILE, Record};
 use aoc_rust::input;
-use aoc_rust::year2025::day12;
+use aoc_rust::random::Rng;
+use aoc_rust::repl;
+use aoc_rust::report;
+use aoc_rust::runner::{
+    Params, Solution, Timing, compare, find_solution, run_all, solutions, time_streamed_part,
+};
+use aoc_rust::status;
+use itertools::Itertools;
+use std::env;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::Path;
+use std::process::exit;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+Usage:
+  aoc-rust                                    run the latest day on its input
+  aoc-rust run [<year> [<day>]] [--test] [--no-cache] [--stream] [--param <name>=<value>]...
+  aoc-rust compare <year> <day> [--test] [--param <name>=<value>]...
+  aoc-rust generate <year> <day> [--size <size>] [--seed <seed>]
+  aoc-rust fuzz [<year> [<day>]] [--iterations <count>] [--seed <seed>]
+  aoc-rust report --year <year> [--output <path>] [--no-cache] [--param <name>=<value>]...
+  aoc-rust status [<year>]
+  aoc-rust history [<year> [<day>]] [--threshold <percent>]
+  aoc-rust repl";
 
 fn main() {
-    let input = input::get_input(2025, 12);
-    let (presents, regions) = day12::handle_input(&input);
-    let res = day12::part1(&presents, &regions);
-    println!("The result is {res}.")
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [] => {
+            let latest = solutions()
+                .into_iter()
+                .max_by_key(|s| (s.year, s.day))
+                .expect("No solutions registered");
+            run(&[latest], false, &Params::default(), true);
+        }
+        ["run", args @ ..] => {
+            let (selected, options) = select(args);
+            if options.stream {
+                run_streamed(&selected, options.test, &options.params);
+            } else {
+                run(&selected, options.test, &options.params, !options.no_cache);
+            }
+        }
+        ["compare", year, day, options @ ..] => {
+            let options = parse_options(options);
+            run_comparison(&solution(year, day), options.test, &options.params);
+        }
+        ["generate", year, day, options @ ..] => {
+            let options = parse_options(options);
+            generate(&solution(year, day), options.size, options.seed);
+        }
+        ["fuzz", args @ ..] => {
+            let (selected, options) = select(args);
+            run_fuzz(&selected, options.iterations, options.seed);
+        }
+        ["report", options @ ..] => {
+            let options = parse_options(options);
+            let year = options
+                .year
+                .unwrap_or_else(|| fail("Expected --year <year>"));
+            let output = options
+                .output
+                .unwrap_or_else(|| format!("report-{year}.html"));
+            write_report(year, &options.params, &output, !options.no_cache);
+        }
+        ["status", args @ ..] => {
+            let (selected, _) = select(args);
+            let cache = load_cache(true);
+            let years = selected.iter().map(|s| s.year).unique();
+            for year in years {
+                let days = status::year_status(year, &selected, cache.as_ref());
+                println!("{}", status::render(year, &days));
+            }
+        }
+        ["history", args @ ..] => {
+            let (selected, options) = select(args);
+            show_history(&selected, options.threshold);
+        }
+        ["repl"] => repl::run(io::stdin().lock(), io::stdout()).expect("Failed to use terminal"),
+        _ => fail(USAGE),
+    }
+}
+
+fn solution(year: &str, day: &str) -> Solution {
+    year.parse()
+        .ok()
+        .zip(day.parse().ok())
+        .and_then(|(year, day)| find_solution(year, day))
+        .unwrap_or_else(|| fail(&format!("No solution for {year} day {day}")))
+}
+
+/// The solutions picked by optional leading `<year> [<day>]` arguments, all
+/// of them by default, and the options after them.
+fn select(args: &[&str]) -> (Vec<Solution>, Options) {
+    let (days, options) = args.split_at(args.iter().take_while(|a| !a.starts_with("--")).count());
+    let selected = match days {
+        [] => solutions(),
+        [year] => {
+            let year: i32 = year.parse().unwrap_or_else(|_| fail("Invalid year"));
+            solutions().into_iter().filter(|s| s.year == year).collect()
+        }
+        [year, day] => vec![solution(year, day)],
+        _ => fail(USAGE),
+    };
+    (selected, parse_options(options))
+}
+
+struct Options {
+    test: bool,
+    no_cache: bool,
+    stream: bool,
+    params: Params,
+    size: usize,
+    seed: Option<u64>,
+    iterations: usize,
+    threshold: f64,
+    year: Option<i32>,
+    output: Option<String>,
+}
+
+fn parse_options(options: &[&str]) -> Options {
+    let mut parsed = Options {
+        test: false,
+        no_cache: false,
+        stream: false,
+        params: Params::default(),
+        size: 100,
+        seed: None,
+        iterations: 10_000,
+        threshold: 10.0,
+        year: None,
+        output: None,
+    };
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        let mut value = || {
+            options
+                .next()
+                .unwrap_or_else(|| fail(&format!("Expected a value after {option}")))
+        };
+        match *option {
+            "--test" => parsed.test = true,
+            "--no-cache" => parsed.no_cache = true,
+            "--stream" => parsed.stream = true,
+            "--param" => {
+                let (name, value) = value()
+                    .split_once('=')
+                    .unwrap_or_else(|| fail("Expected <name>=<value> after --param"));
+                parsed.params.set(name, value);
+            }
+            "--size" => parsed.size = value().parse().unwrap_or_else(|_| fail("Invalid size")),
+            "--seed" => {
+                parsed.seed = Some(value().parse().unwrap_or_else(|_| fail("Invalid seed")))
+            }
+            "--iterations" => {
+                parsed.iterations = value()
+                    .parse()
+                    .unwrap_or_else(|_| fail("Invalid iterations"))
+            }
+            "--threshold" => {
+                parsed.threshold = value()
+                    .parse()
+                    .unwrap_or_else(|_| fail("Invalid threshold"))
+            }
+            "--year" => {
+                parsed.year = Some(value().parse().unwrap_or_else(|_| fail("Invalid year")))
+            }
+            "--output" => parsed.output = Some(value().to_string()),
+            _ => fail(&format!("Unknown option {option}\n{USAGE}")),
+        }
+    }
+    parsed
+}
+
+fn read_input(solution: &Solution, test: bool) -> String {
+    let input = if test {
+        input::read_test_input(solution.year, solution.day)
+    } else {
+        input::read_input(solution.year, solution.day)
+    };
+    input.unwrap_or_else(|e| fail(&e))
+}
+
+fn run(solutions: &[Solution], test: bool, params: &Params, use_cache: bool) {
+    let mut failed = false;
+    let mut cache = load_cache(use_cache);
+    for run in run_all(solutions, test, params, cache.as_mut()) {
+        let (year, day) = (run.solution.year, run.solution.day);
+        if let Err(e) = &run.input {
+            eprintln!("{year} day {day}: {e}");
+            failed = true;
+            continue;
+        }
+        for timing in &run.timings {
+            let name = format!("{year} day {day} part {}", timing.part);
+            match &timing.answer {
+                Ok(res) if timing.cached => println!("{name}: {res} (cached)"),
+                Ok(res) => println!("{name}: {res}"),
+                Err(e) => {
+                    eprintln!("{name} failed: {e}");
+                    failed = true;
+                }
+            }
+        }
+        if !test {
+            record_history(run.solution, &run.timings);
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+/// Runs the days that can stream their input without loading it whole. The
+/// answers are neither cached nor recorded in the history.
+fn run_streamed(solutions: &[Solution], test: bool, params: &Params) {
+    let mut failed = false;
+    let streamed: Vec<_> = solutions
+        .iter()
+        .filter(|solution| solution.main_parts().any(|part| part.stream.is_some()))
+        .collect();
+    if streamed.is_empty() {
+        fail("None of these days can stream its input");
+    }
+    for solution in streamed {
+        for part in solution.main_parts() {
+            let timing = time_streamed_part(solution, part, test, params);
+            let name = format!("{} day {} part {}", solution.year, solution.day, part.part);
+            match &timing.answer {
+                Ok(res) => println!("{name}: {res} ({:.3?})", timing.time),
+                Err(e) => {
+                    eprintln!("{name} failed: {e}");
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        exit(1);
+    }
+}
+
+fn run_comparison(solution: &Solution, test: bool, params: &Params) {
+    let input = read_input(solution, test);
+    let mut all_agree = true;
+    let comparisons = compare(solution, &input, params);
+    for comparison in &comparisons {
+        let verdict = if comparison.agrees() {
+            "answers agree"
+        } else {
+            all_agree = false;
+            "ANSWERS DIFFER"
+        };
+        println!(
+            "{} day {} part {}: {verdict}",
+            solution.year, solution.day, comparison.part
+        );
+        for (rank, timing) in comparison.timings.iter().enumerate() {
+            let answer = match &timing.answer {
+                Ok(answer) => answer.clone(),
+                Err(e) => format!("error: {}", e.lines().next().unwrap_or_default()),
+            };
+            println!(
+                "  {}. {:<12} {answer:>20} {:>12.3?}",
+                rank + 1,
+                timing.name,
+                timing.time
+            );
+        }
+    }
+    if !test {
+        let timings: Vec<_> = comparisons.into_iter().flat_map(|c| c.timings).collect();
+        record_history(solution, &timings);
+    }
+    if !all_agree {
+        exit(1);
+    }
+}
+
+/// The answers cached by this build, unless caching is turned off.
+fn load_cache(use_cache: bool) -> Option<Cache> {
+    let build = build_id().filter(|_| use_cache)?;
+    Some(Cache::load(Path::new(CACHE_FILE), &build))
+}
+
+/// Appends the successful timings to the history file. Runs on the example
+/// input are not recorded, they are too short to say anything, and neither
+/// are cached answers.
+fn record_history(solution: &Solution, timings: &[Timing]) {
+    let records: Vec<_> = timings
+        .iter()
+        .filter(|timing| timing.answer.is_ok() && !timing.cached)
+        .map(|timing| Record::new(&commit(), solution.year, solution.day, timing))
+        .collect();
+    if let Err(e) = history::append(Path::new(HISTORY_FILE), &records) {
+        eprintln!("Could not record timings in {HISTORY_FILE}: {e}");
+    }
+}
+
+/// Runs the whole year, records the timings and writes the report including
+/// them.
+fn write_report(year: i32, params: &Params, output: &str, use_cache: bool) {
+    let solutions: Vec<_> = solutions().into_iter().filter(|s| s.year == year).collect();
+    if solutions.is_empty() {
+        fail(&format!("No solutions for {year}"));
+    }
+    let mut cache = load_cache(use_cache);
+    let runs = run_all(&solutions, false, params, cache.as_mut());
+    for run in &runs {
+        record_history(run.solution, &run.timings);
+    }
+    let records = history::load(Path::new(HISTORY_FILE)).unwrap_or_else(|e| fail(&e));
+    let html = report::render(year, &commit(), &runs, &history::trends(&records));
+    fs::write(output, html).unwrap_or_else(|e| fail(&format!("Cannot write {output}: {e}")));
+    println!("Wrote {output}");
+}
+
+fn commit() -> String {
+    history::git_commit(Path::new(".")).unwrap_or_else(|| "unknown".to_string())
+}
+
+fn show_history(solutions: &[Solution], threshold: f64) {
+    let records = history::load(Path::new(HISTORY_FILE)).unwrap_or_else(|e| fail(&e));
+    let records: Vec<_> = records
+        .into_iter()
+        .filter(|r| solutions.iter().any(|s| s.year == r.year && s.day == r.day))
+        .collect();
+    let mut regressed = false;
+    for trend in history::trends(&records) {
+        let mut name = format!("{} day {} part {}", trend.year, trend.day, trend.part);
+        if trend.name != "main" {
+            name += &format!(" ({})", trend.name);
+        }
+        let (best_commit, best) = trend.best();
+        let (latest_commit, latest) = trend.latest();
+        let recent = trend.commits.iter().rev().take(8).rev();
+        let recent = recent
+            .map(|(_, time)| format!("{time:.1?}"))
+            .collect::<Vec<_>>();
+        let verdict = if trend.regressed(threshold) {
+            regressed = true;
+            " SLOWER"
+        } else {
+            ""
+        };
+        println!(
+            "{name:<28} best {best:>10.3?} @{:<8} latest {latest:>10.3?} @{:<8} {:>+7.1}%{verdict}",
+            &best_commit[..best_commit.len().min(8)],
+            &latest_commit[..latest_commit.len().min(8)],
+            trend.slowdown()
+        );
+        println!("{:<28} trend {}", "", recent.join(" → "));
+    }
+    if regressed {
+        exit(1);
+    }
+}
+
+fn generate(solution: &Solution, size: usize, seed: Option<u64>) {
+    let seed = seed.unwrap_or_else(random_seed);
+    eprintln!("Generating with seed {seed}");
+    let mut rng = Rng::new(seed);
+    print!("{}", (solution.generate)(&mut rng, size));
+}
+
+fn run_fuzz(solutions: &[Solution], iterations: usize, seed: Option<u64>) {
+    let seed = seed.unwrap_or_else(random_seed);
+    eprintln!("Fuzzing with seed {seed}");
+    // The crashes are reported below, the default hook would print each one
+    // as it happens.
+    panic::set_hook(Box::new(|_| {}));
+    let mut rng = Rng::new(seed);
+    let mut crashed = false;
+    for solution in solutions {
+        let crashes = fuzz::fuzz(solution, &mut rng, iterations);
+        println!(
+            "{} day {}: {} crashes in {iterations} inputs",
+            solution.year,
+            solution.day,
+            crashes.len()
+        );
+        for crash in &crashes {
+            println!(
+                "  panicked with {:?} on input {:?}",
+                crash.message, crash.input
+            );
+        }
+        crashed |= !crashes.is_empty();
+    }
+    if crashed {
+        exit(1);
+    }
+}
+
+fn random_seed() -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    now.as_nanos() as u64
+}
+
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    exit(1)
 }
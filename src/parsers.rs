@@ -0,0 +1,1291 @@
+pub use aoc_derive::PuzzleParse;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::{Enumerate, Peekable, repeat};
+use std::ops::Range;
+use std::str::{FromStr, Lines};
+
+/// Where and why an input could not be parsed. Lines and columns start at 1,
+/// columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    /// The offending text, empty when the input stops too early.
+    pub text: String,
+    pub expected: String,
+    /// The whole line the error is on, for showing it.
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// An error about `text`, which must be a slice of `input`: its position
+    /// in `input` gives the line and the column.
+    pub fn at(input: &str, text: &str, expected: impl Display) -> ParseError {
+        let offset = offset(input, text);
+        debug_assert!(offset <= input.len(), "{text:?} is not part of the input");
+        let before = input.get(..offset).unwrap_or_default();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let source_line = input[line_start..].lines().next().unwrap_or_default();
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: text.to_string(),
+            expected: expected.to_string(),
+            source_line: source_line.to_string(),
+        }
+    }
+
+    /// An error for an input that stops before `expected`.
+    pub fn end(input: &str, expected: impl Display) -> ParseError {
+        ParseError::at(input, &input[input.len()..], expected)
+    }
+
+    /// Says what was being read, as in "expected '#' in present 3".
+    pub fn context(mut self, context: impl Display) -> ParseError {
+        self.expected = format!("{} in {context}", self.expected);
+        self
+    }
+
+    /// The error like a compiler diagnostic: the message, then the line with
+    /// a caret under the offending text.
+    ///
+    /// ```text
+    /// line 2, column 1: expected 'L' or 'R', found "X"
+    ///   |
+    /// 2 | X12
+    ///   | ^
+    /// ```
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        // Keep tabs so that the caret lines up with the text above it.
+        let indent: String = self
+            .source_line
+            .chars()
+            .take(self.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let available = self
+            .source_line
+            .chars()
+            .count()
+            .saturating_sub(self.column - 1);
+        let width = self
+            .text
+            .lines()
+            .next()
+            .map_or(0, |text| text.chars().count());
+        let carets = "^".repeat(width.min(available).max(1));
+        format!(
+            "{self}\n{gutter} |\n{number} | {}\n{gutter} | {indent}{carets}",
+            self.source_line
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, ", found nothing")
+        } else {
+            write!(f, ", found {:?}", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse errors reach the runner as strings, so this is where every day's
+/// errors get rendered with the offending line.
+impl From<ParseError> for String {
+    fn from(error: ParseError) -> String {
+        error.render()
+    }
+}
+
+/// Where `text`, a slice of `input`, starts in `input`.
+fn offset(input: &str, text: &str) -> usize {
+    (text.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize)
+}
+
+/// Parses `text`, a slice of `input`, as a number.
+pub fn parse_number<T: FromStr>(input: &str, text: &str, expected: &str) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::at(input, text, expected))
+}
+
+/// The character of `text` starting at byte `index`, as a slice.
+pub fn char_at(text: &str, index: usize) -> &str {
+    let len = text[index..].chars().next().map_or(0, char::len_utf8);
+    &text[index..index + len]
+}
+
+/// Splits `text`, a slice of `input`, into the content of the group that
+/// starts it, between `open` and the matching `close`, and the text after
+/// that group. Groups can nest, as in `[[1,2],[3]]`.
+pub fn delimited_group<'a>(
+    input: &str,
+    text: &'a str,
+    open: char,
+    close: char,
+) -> Result<(&'a str, &'a str), ParseError> {
+    let Some(content) = text.strip_prefix(open) else {
+        return Err(ParseError::at(input, char_at(text, 0), format!("'{open}'")));
+    };
+    let mut depth = 0;
+    for (i, c) in content.char_indices() {
+        if c == close {
+            if depth == 0 {
+                return Ok((&content[..i], &content[i + close.len_utf8()..]));
+            }
+            depth -= 1;
+        } else if c == open {
+            depth += 1;
+        }
+    }
+    let expected = format!("a '{close}' matching this '{open}'");
+    Err(ParseError::at(input, char_at(text, 0), expected))
+}
+
+/// The content of every group of `text` between `open` and `close`, such as
+/// all the buttons of a day 10 machine. The text between groups is ignored.
+pub fn groups<'a>(
+    input: &str,
+    text: &'a str,
+    open: char,
+    close: char,
+) -> Result<Vec<&'a str>, ParseError> {
+    let mut groups = Vec::new();
+    let mut rest = text;
+    while let Some(i) = rest.find([open, close]) {
+        if !rest[i..].starts_with(open) {
+            let expected = format!("a '{open}' before this '{close}'");
+            return Err(ParseError::at(input, char_at(rest, i), expected));
+        }
+        let (group, after) = delimited_group(input, &rest[i..], open, close)?;
+        groups.push(group);
+        rest = after;
+    }
+    Ok(groups)
+}
+
+/// Parses the content of `text`, a single group between `start` and `end`.
+pub fn parse_with_delimiters<'a, T>(
+    input: &str,
+    start: char,
+    end: char,
+    text: &'a str,
+    parser: impl Fn(&'a str) -> Result<T, ParseError>,
+) -> Result<T, ParseError> {
+    let (content, rest) = delimited_group(input, text, start, end)?;
+    if !rest.is_empty() {
+        return Err(ParseError::at(input, rest, "the end of the group"));
+    }
+    parser(content)
+}
+
+/// Why a combinator failed: what it expected and the text it found instead,
+/// a slice of the text being parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure<'a> {
+    pub found: &'a str,
+    pub expected: String,
+}
+
+impl<'a> Failure<'a> {
+    fn at_start_of(&self, text: &str) -> bool {
+        self.found.as_ptr() == text.as_ptr()
+    }
+
+    /// A failure on the first character of `rest`, or on nothing at its end.
+    pub fn new(rest: &'a str, expected: impl Display) -> Failure<'a> {
+        Failure {
+            found: char_at(rest, 0),
+            expected: expected.to_string(),
+        }
+    }
+
+    pub fn into_error(self, input: &str) -> ParseError {
+        ParseError::at(input, self.found, self.expected)
+    }
+}
+
+/// A parsed value and the text left after it.
+pub type Step<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// Parses a value from the start of a text. Any function from the text to a
+/// [`Step`] is a parser; the functions below build and combine them, and
+/// references to the parsers they return are parsers too.
+///
+/// A parser that fails on the first character of its text did not match, so
+/// [`opt`], [`alt`], [`many`] and [`separated`] try something else. One that
+/// fails further in has found a mistake, which they report.
+pub trait Parser<'a, T> {
+    fn parse(&self, text: &'a str) -> Step<'a, T>;
+}
+
+impl<'a, T, F: Fn(&'a str) -> Step<'a, T>> Parser<'a, T> for F {
+    fn parse(&self, text: &'a str) -> Step<'a, T> {
+        self(text)
+    }
+}
+
+/// Runs `parser` on the whole of `text`, a slice of `input`.
+pub fn parse_all<'a, T>(
+    input: &str,
+    text: &'a str,
+    parser: impl Parser<'a, T>,
+) -> Result<T, ParseError> {
+    match parser.parse(text) {
+        Ok((value, "")) => Ok(value),
+        Ok((_, rest)) => Err(ParseError::at(input, rest, "the end")),
+        Err(failure) => Err(failure.into_error(input)),
+    }
+}
+
+/// Exactly `literal`.
+pub fn token<'a>(literal: &'static str) -> impl Fn(&'a str) -> Step<'a, &'a str> {
+    move |text: &'a str| match text.strip_prefix(literal) {
+        Some(rest) => Ok((&text[..literal.len()], rest)),
+        None if literal.chars().count() == 1 => Err(Failure::new(text, format!("'{literal}'"))),
+        None => Err(Failure::new(text, format!("{literal:?}"))),
+    }
+}
+
+/// A number made of ASCII digits.
+pub fn unsigned<'a, T: FromStr>() -> impl Fn(&'a str) -> Step<'a, T> {
+    move |text: &'a str| number(text, 0)
+}
+
+/// A number made of ASCII digits, optionally preceded by a sign.
+pub fn signed<'a, T: FromStr>() -> impl Fn(&'a str) -> Step<'a, T> {
+    move |text: &'a str| {
+        let sign = usize::from(text.starts_with(['-', '+']));
+        number(text, sign)
+    }
+}
+
+fn number<'a, T: FromStr>(text: &'a str, sign: usize) -> Step<'a, T> {
+    let digits = text[sign..].bytes().take_while(u8::is_ascii_digit).count();
+    if digits == 0 {
+        return Err(Failure::new(&text[sign..], "a number"));
+    }
+    let (number, rest) = text.split_at(sign + digits);
+    match number.parse() {
+        Ok(value) => Ok((value, rest)),
+        Err(_) => Err(Failure {
+            found: number,
+            expected: "a smaller number".to_string(),
+        }),
+    }
+}
+
+pub fn map<'a, A, B>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Fn(&'a str) -> Step<'a, B> {
+    move |text: &'a str| {
+        let (value, rest) = parser.parse(text)?;
+        Ok((f(value), rest))
+    }
+}
+
+/// Like [`map`] for a conversion that can fail, in which case the error
+/// points at the whole text `parser` consumed.
+pub fn try_map<'a, A, B, E: Display>(
+    parser: impl Parser<'a, A>,
+    f: impl Fn(A) -> Result<B, E>,
+) -> impl Fn(&'a str) -> Step<'a, B> {
+    move |text: &'a str| {
+        let (value, rest) = parser.parse(text)?;
+        match f(value) {
+            Ok(value) => Ok((value, rest)),
+            Err(expected) => Err(Failure {
+                found: &text[..text.len() - rest.len()],
+                expected: expected.to_string(),
+            }),
+        }
+    }
+}
+
+/// The value of `parser` along with the text it consumed.
+pub fn consumed<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(&'a str) -> Step<'a, (T, &'a str)> {
+    move |text: &'a str| {
+        let (value, rest) = parser.parse(text)?;
+        Ok(((value, &text[..text.len() - rest.len()]), rest))
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(&'a str) -> Step<'a, (A, B)> {
+    move |text: &'a str| {
+        let (a, rest) = first.parse(text)?;
+        let (b, rest) = second.parse(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// `second`, after `first` whose value is dropped.
+pub fn preceded<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(&'a str) -> Step<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// `first`, followed by `second` whose value is dropped.
+pub fn terminated<'a, A, B>(
+    first: impl Parser<'a, A>,
+    second: impl Parser<'a, B>,
+) -> impl Fn(&'a str) -> Step<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// `inner` between `open` and `close`.
+pub fn delimited<'a, A, T, B>(
+    open: impl Parser<'a, A>,
+    inner: impl Parser<'a, T>,
+    close: impl Parser<'a, B>,
+) -> impl Fn(&'a str) -> Step<'a, T> {
+    preceded(open, terminated(inner, close))
+}
+
+/// `parser` if it matches, otherwise nothing without consuming any text.
+pub fn opt<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(&'a str) -> Step<'a, Option<T>> {
+    move |text: &'a str| match parser.parse(text) {
+        Ok((value, rest)) => Ok((Some(value), rest)),
+        Err(failure) if failure.at_start_of(text) => Ok((None, text)),
+        Err(failure) => Err(failure),
+    }
+}
+
+/// `first`, or `second` when `first` does not match. When both fail on the
+/// same text, the error lists what either expected.
+pub fn alt<'a, T>(
+    first: impl Parser<'a, T>,
+    second: impl Parser<'a, T>,
+) -> impl Fn(&'a str) -> Step<'a, T> {
+    move |text: &'a str| {
+        first.parse(text).or_else(|a| {
+            if !a.at_start_of(text) {
+                return Err(a);
+            }
+            second.parse(text).map_err(|b| {
+                if b.at_start_of(text) {
+                    Failure {
+                        found: a.found,
+                        expected: format!("{} or {}", a.expected, b.expected),
+                    }
+                } else {
+                    b
+                }
+            })
+        })
+    }
+}
+
+/// `parser` as many times as it matches, possibly none.
+pub fn many<'a, T>(parser: impl Parser<'a, T>) -> impl Fn(&'a str) -> Step<'a, Vec<T>> {
+    move |mut text: &'a str| {
+        let mut values = Vec::new();
+        loop {
+            match parser.parse(text) {
+                Ok((_, rest)) if rest.len() == text.len() => break,
+                Ok((value, rest)) => {
+                    values.push(value);
+                    text = rest;
+                }
+                Err(failure) if failure.at_start_of(text) => break,
+                Err(failure) => return Err(failure),
+            }
+        }
+        Ok((values, text))
+    }
+}
+
+/// One or more `item`s with a `separator` between each of them.
+pub fn separated<'a, T, S>(
+    item: impl Parser<'a, T>,
+    separator: impl Parser<'a, S>,
+) -> impl Fn(&'a str) -> Step<'a, Vec<T>> {
+    move |text: &'a str| {
+        let (first, mut text) = item.parse(text)?;
+        let mut values = vec![first];
+        loop {
+            let rest = match separator.parse(text) {
+                Ok((_, rest)) => rest,
+                Err(failure) if failure.at_start_of(text) => break,
+                Err(failure) => return Err(failure),
+            };
+            let (value, rest) = item.parse(rest)?;
+            values.push(value);
+            text = rest;
+        }
+        Ok((values, text))
+    }
+}
+
+/// A value that can be read from a piece of an input, such as a field of a
+/// [`scan!`] pattern. Structs can derive it, see [`aoc_derive::PuzzleParse`].
+pub trait PuzzleParse<'a>: Sized {
+    fn read(input: &str, text: &'a str) -> Result<Self, ParseError>;
+}
+
+impl<'a> PuzzleParse<'a> for &'a str {
+    fn read(_input: &str, text: &'a str) -> Result<Self, ParseError> {
+        Ok(text)
+    }
+}
+
+macro_rules! parsed_fields {
+    ($($t:ty => $expected:literal),+) => {$(
+        impl PuzzleParse<'_> for $t {
+            fn read(input: &str, text: &str) -> Result<Self, ParseError> {
+                parse_number(input, text, $expected)
+            }
+        }
+    )+};
+}
+
+parsed_fields!(
+    u8 => "a number", u16 => "a number", u32 => "a number", u64 => "a number",
+    u128 => "a number", usize => "a number", i8 => "a number", i16 => "a number",
+    i32 => "a number", i64 => "a number", i128 => "a number", isize => "a number",
+    f64 => "a number", char => "a character", String => "some text"
+);
+
+/// The number of `{}` fields in a [`scan!`] pattern.
+pub const fn count_fields(pattern: &str) -> usize {
+    let bytes = pattern.as_bytes();
+    let (mut i, mut count) = (0, 0);
+    while i + 1 < bytes.len() {
+        if bytes[i] == b'{' && bytes[i + 1] == b'}' {
+            count += 1;
+            i += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+/// Matches `text`, a slice of `input`, against `pattern` and returns the text
+/// of each `{}` field. A field ends where the text following it in the
+/// pattern first appears, so fields must be separated by some text.
+pub fn scan_fields<'a>(
+    input: &str,
+    text: &'a str,
+    pattern: &str,
+) -> Result<Vec<&'a str>, ParseError> {
+    debug_assert!(
+        !pattern.contains("{}{}"),
+        "the fields of {pattern:?} are not separated"
+    );
+    let mut literals = pattern.split("{}");
+    let first = literals.next().unwrap_or_default();
+    let mut rest = text
+        .strip_prefix(first)
+        .ok_or_else(|| ParseError::at(input, text, format!("{pattern:?}")))?;
+    let mut fields = Vec::new();
+    for literal in literals {
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| ParseError::at(input, rest, format!("{literal:?}")))?
+        };
+        fields.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(ParseError::at(input, rest, "the end"));
+    }
+    Ok(fields)
+}
+
+/// Matches `text`, a slice of `input`, against a pattern like `"{}x{}: {}"`
+/// and reads each `{}` field as the corresponding type, giving a tuple.
+/// Errors point at the text that does not match.
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $text:expr, $pattern:literal, $($t:ty),+ $(,)?) => {{
+        const _: () = assert!(
+            $crate::parsers::count_fields($pattern) == [$(stringify!($t)),+].len(),
+            "the pattern needs one type per field"
+        );
+        let input: &str = $input;
+        $crate::parsers::scan_fields(input, $text, $pattern).and_then(|fields| {
+            let mut fields = fields.into_iter();
+            Ok(($(
+                <$t as $crate::parsers::PuzzleParse>::read(input, fields.next().unwrap_or_default())?,
+            )+))
+        })
+    }};
+}
+
+/// A block of consecutive non-blank lines of an input.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Section<'a> {
+    /// The number of the first line in the input, starting at 1.
+    pub line: usize,
+    /// The lines of the section, without the final line break.
+    pub text: &'a str,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> Lines<'a> {
+        self.text.lines()
+    }
+
+    /// Checks that the first line is `header` and gives the lines after it.
+    pub fn header(&self, input: &str, header: &str) -> Result<Lines<'a>, ParseError> {
+        let mut lines = self.lines();
+        match lines.next() {
+            Some(line) if line == header => Ok(lines),
+            line => Err(ParseError::at(
+                input,
+                line.unwrap_or(self.text),
+                format!("{header:?}"),
+            )),
+        }
+    }
+
+    /// The empty text after the last line, for errors about missing lines.
+    pub fn end(&self) -> &'a str {
+        &self.text[self.text.len()..]
+    }
+}
+
+/// The sections of an input separated by blank lines, see [`sections`].
+pub struct Sections<'a> {
+    input: &'a str,
+    lines: Peekable<Enumerate<Lines<'a>>>,
+}
+
+/// Splits `input` into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        input,
+        lines: input.lines().enumerate().peekable(),
+    }
+}
+
+impl<'a> Sections<'a> {
+    /// The next section, or an error saying that `expected` is missing.
+    pub fn next_section(&mut self, expected: impl Display) -> Result<Section<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::end(self.input, expected))
+    }
+
+    /// Checks that there are no sections left.
+    pub fn finish(mut self) -> Result<(), ParseError> {
+        match self.next() {
+            Some(section) => Err(ParseError::at(
+                self.input,
+                section.lines().next().unwrap_or_default(),
+                "the end of the input",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        while self
+            .lines
+            .next_if(|(_, line)| line.trim().is_empty())
+            .is_some()
+        {}
+        let (number, first) = self.lines.next()?;
+        let mut last = first;
+        while let Some((_, line)) = self.lines.next_if(|(_, line)| !line.trim().is_empty()) {
+            last = line;
+        }
+        let start = offset(self.input, first);
+        let end = offset(self.input, last) + last.len();
+        Some(Section {
+            line: number + 1,
+            text: &self.input[start..end],
+        })
+    }
+}
+
+/// Text laid out in fixed-width columns, such as a worksheet of aligned
+/// numbers. Columns are separated by character positions that are blank on
+/// every line, and lines can stop early or carry trailing whitespace.
+#[derive(Debug, Clone)]
+pub struct Table<'a> {
+    lines: Vec<&'a str>,
+    columns: Vec<Range<usize>>,
+}
+
+/// How the text of a cell sits within the width of its column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Right,
+    /// The text fills the whole width.
+    Full,
+    /// Blank on both sides.
+    Centered,
+    Empty,
+}
+
+/// The text of one line within one column, as a slice of the line. It is
+/// shorter than the column when the line stops early.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell<'a> {
+    pub text: &'a str,
+    pub width: usize,
+}
+
+impl<'a> Cell<'a> {
+    /// The characters of the cell, padded with spaces to the column width.
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        self.text.chars().chain(repeat(' ')).take(self.width)
+    }
+
+    pub fn trimmed(&self) -> &'a str {
+        self.text.trim()
+    }
+
+    pub fn alignment(&self) -> Alignment {
+        let first = self.chars().next().is_some_and(|c| !c.is_whitespace());
+        let last = self.chars().last().is_some_and(|c| !c.is_whitespace());
+        match (first, last) {
+            (true, true) => Alignment::Full,
+            (true, false) => Alignment::Left,
+            (false, true) => Alignment::Right,
+            _ if self.trimmed().is_empty() => Alignment::Empty,
+            _ => Alignment::Centered,
+        }
+    }
+}
+
+impl<'a> Table<'a> {
+    pub fn new(lines: Vec<&'a str>) -> Table<'a> {
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut filled = vec![false; width];
+        for line in &lines {
+            for (i, c) in line.chars().enumerate() {
+                filled[i] |= !c.is_whitespace();
+            }
+        }
+        let mut columns = Vec::new();
+        let mut start = None;
+        for (i, filled) in filled.iter().chain([&false]).enumerate() {
+            match (start, filled) {
+                (None, true) => start = Some(i),
+                (Some(column_start), false) => {
+                    columns.push(column_start..i);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        Table { lines, columns }
+    }
+
+    /// The character positions of each column.
+    pub fn columns(&self) -> &[Range<usize>] {
+        &self.columns
+    }
+
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    pub fn cell(&self, row: usize, column: usize) -> Cell<'a> {
+        let line = self.lines[row];
+        let Range { start, end } = self.columns[column];
+        let byte = |i| {
+            line.char_indices()
+                .nth(i)
+                .map_or(line.len(), |(byte, _)| byte)
+        };
+        let text = if line.is_ascii() {
+            &line[start.min(line.len())..end.min(line.len())]
+        } else {
+            &line[byte(start)..byte(end)]
+        };
+        Cell {
+            text,
+            width: end - start,
+        }
+    }
+
+    /// The cells line by line.
+    pub fn rows(&self) -> impl Iterator<Item = Vec<Cell<'a>>> + '_ {
+        (0..self.lines.len()).map(|row| {
+            (0..self.columns.len())
+                .map(|column| self.cell(row, column))
+                .collect()
+        })
+    }
+
+    /// The cells of one column, from the first line to the last.
+    pub fn column(&self, column: usize) -> Vec<Cell<'a>> {
+        (0..self.lines.len())
+            .map(|row| self.cell(row, column))
+            .collect()
+    }
+}
+
+/// Reads an input byte by byte, for inputs large enough that going through
+/// `str::lines`, `split` and `parse` shows. The cursor only ever stops on
+/// ASCII bytes, so errors can still point at the text.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Cursor<'a> {
+        Cursor { input, position: 0 }
+    }
+
+    /// The bytes that have not been read yet.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.input.as_bytes()[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == self.input.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.rest().first().copied()
+    }
+
+    /// An error about the character under the cursor.
+    pub fn error(&self, expected: impl Display) -> ParseError {
+        ParseError::at(self.input, char_at(self.input, self.position), expected)
+    }
+
+    /// Reads a number made of ASCII digits.
+    pub fn unsigned(&mut self) -> Result<u64, ParseError> {
+        let rest = self.rest();
+        let digits = rest.iter().take_while(|byte| byte.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(self.error("a number"));
+        }
+        let number = &rest[..digits];
+        let value = if digits < 20 {
+            // Up to 19 digits always fit in a u64.
+            Some(
+                number
+                    .iter()
+                    .fold(0, |value, digit| value * 10 + (digit - b'0') as u64),
+            )
+        } else {
+            number.iter().try_fold(0u64, |value, digit| {
+                value.checked_mul(10)?.checked_add((digit - b'0') as u64)
+            })
+        };
+        let start = self.position;
+        self.position += digits;
+        value.ok_or_else(|| {
+            ParseError::at(
+                self.input,
+                &self.input[start..self.position],
+                "a smaller number",
+            )
+        })
+    }
+
+    /// Reads a number made of ASCII digits, optionally preceded by a sign.
+    pub fn signed(&mut self) -> Result<i64, ParseError> {
+        let negative = self.peek() == Some(b'-');
+        if negative || self.peek() == Some(b'+') {
+            self.position += 1;
+        }
+        let start = self.position;
+        let value = self.unsigned()?;
+        let value = if negative {
+            0i64.checked_sub_unsigned(value)
+        } else {
+            i64::try_from(value).ok()
+        };
+        value.ok_or_else(|| {
+            ParseError::at(
+                self.input,
+                &self.input[start..self.position],
+                "a smaller number",
+            )
+        })
+    }
+
+    /// Skips one `byte`, returning whether it was there.
+    pub fn skip(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        self.position += found as usize;
+        found
+    }
+
+    /// Skips every leading byte in `bytes`, such as separators between numbers.
+    pub fn skip_all(&mut self, bytes: &[u8]) {
+        while self.peek().is_some_and(|byte| bytes.contains(&byte)) {
+            self.position += 1;
+        }
+    }
+
+    /// Reads one `byte` or fails.
+    pub fn expect(&mut self, byte: u8) -> Result<(), ParseError> {
+        if self.skip(byte) {
+            Ok(())
+        } else {
+            Err(self.error(format!("'{}'", byte as char)))
+        }
+    }
+
+    /// The next line without its line break, or `None` at the end.
+    pub fn line(&mut self) -> Option<&'a [u8]> {
+        if self.is_empty() {
+            return None;
+        }
+        let rest = self.rest();
+        let end = self.line_end();
+        self.position += (end + 1).min(rest.len());
+        let line = &rest[..end];
+        Some(line.strip_suffix(b"\r").unwrap_or(line))
+    }
+
+    /// Reads the digits up to the end of the line as values from 0 to 9.
+    pub fn digit_row(&mut self) -> Result<Vec<u8>, ParseError> {
+        let rest = self.rest();
+        let end = self.line_end();
+        let line = &rest[..end];
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if let Some(i) = line.iter().position(|byte| !byte.is_ascii_digit()) {
+            let bad = char_at(self.input, self.position + i);
+            return Err(ParseError::at(self.input, bad, "a digit"));
+        }
+        self.position += (end + 1).min(rest.len());
+        Ok(line.iter().map(|digit| digit - b'0').collect())
+    }
+
+    /// Where the current line ends in the rest of the input.
+    fn line_end(&self) -> usize {
+        let rest = &self.input[self.position..];
+        rest.find('\n').unwrap_or(rest.len())
+    }
+}
+
+/// The pieces of the parsers generated by `#[derive(PuzzleParse)]`.
+pub mod derive {
+    use super::{ParseError, PuzzleParse, char_at, delimited_group};
+
+    /// Adds the name of the field being read to its errors.
+    pub fn in_field<T>(result: Result<T, ParseError>, field: &str) -> Result<T, ParseError> {
+        result.map_err(|mut error| {
+            error.expected = format!("{} for `{field}`", error.expected);
+            error
+        })
+    }
+
+    /// Splits `text` before the first `separator`, or at its end.
+    pub fn until<'a>(text: &'a str, separator: Option<&str>) -> (&'a str, &'a str) {
+        let end = separator
+            .and_then(|separator| text.find(separator))
+            .unwrap_or(text.len());
+        text.split_at(end)
+    }
+
+    pub fn separator<'a>(
+        input: &str,
+        text: &'a str,
+        separator: &str,
+    ) -> Result<&'a str, ParseError> {
+        text.strip_prefix(separator)
+            .ok_or_else(|| ParseError::at(input, char_at(text, 0), format!("{separator:?}")))
+    }
+
+    /// The groups between `open` and `close` at the start of `text`, each
+    /// preceded by `separator` unless it is the `first` field.
+    pub fn groups<'a>(
+        input: &str,
+        text: &'a str,
+        open: char,
+        close: char,
+        separator: &str,
+        first: bool,
+    ) -> Result<(Vec<&'a str>, &'a str), ParseError> {
+        let mut groups = Vec::new();
+        let mut rest = text;
+        loop {
+            let next = if first && groups.is_empty() {
+                Some(rest)
+            } else {
+                rest.strip_prefix(separator)
+            };
+            match next {
+                Some(group) if group.starts_with(open) => {
+                    let (content, after) = delimited_group(input, group, open, close)?;
+                    groups.push(content);
+                    rest = after;
+                }
+                _ => return Ok((groups, rest)),
+            }
+        }
+    }
+
+    pub fn list<'a, T: PuzzleParse<'a>>(
+        input: &str,
+        text: &'a str,
+        separator: &str,
+    ) -> Result<Vec<T>, ParseError> {
+        text.split(separator)
+            .map(|value| T::read(input, value))
+            .collect()
+    }
+
+    pub fn end(input: &str, rest: &str) -> Result<(), ParseError> {
+        if rest.is_empty() {
+            Ok(())
+        } else {
+            Err(ParseError::at(input, rest, "the end"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn locates_errors() {
+        let input = "1,2\n3é,x\n";
+        let x = &input[input.find('x').unwrap()..][..1];
+        let error = ParseError::at(input, x, "a number");
+        assert_eq!((error.line, error.column), (2, 4));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number, found \"x\""
+        );
+        let error = ParseError::end(input, "a number");
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected a number, found nothing"
+        );
+    }
+
+    #[test]
+    pub fn delimiters() {
+        let input = "[1] (2";
+        let number = |text| parse_number::<u32>(input, text, "a number");
+        assert_eq!(
+            parse_with_delimiters(input, '[', ']', &input[..3], number),
+            Ok(1)
+        );
+        let error = parse_with_delimiters(input, '(', ')', &input[4..], number).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 5: expected a ')' matching this '(', found \"(\""
+        );
+    }
+
+    #[test]
+    pub fn nested_groups() {
+        let input = "[[1,2],[3]]x";
+        let (content, rest) = delimited_group(input, input, '[', ']').unwrap();
+        assert_eq!((content, rest), ("[1,2],[3]", "x"));
+        assert_eq!(groups(input, content, '[', ']').unwrap(), ["1,2", "3"]);
+        let error = delimited_group(input, rest, '[', ']').unwrap_err();
+        assert_eq!((error.column, error.expected.as_str()), (12, "'['"));
+
+        let input = "«é«€»» «🎄»";
+        assert_eq!(groups(input, input, '«', '»').unwrap(), ["é«€»", "🎄"]);
+        let error = groups(input, &input[2..], '«', '»').unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 6: expected a '«' before this '»', found \"»\""
+        );
+    }
+
+    #[test]
+    pub fn button_groups() {
+        let line = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}";
+        assert_eq!(
+            groups(line, line, '(', ')').unwrap(),
+            ["3", "1,3", "2", "2,3", "0,2", "0,1"]
+        );
+        let error = groups(line, &line[..19], '(', ')').unwrap_err();
+        assert_eq!((error.column, error.text.as_str()), (18, "("));
+    }
+
+    #[test]
+    pub fn numbers() {
+        assert_eq!(unsigned::<u32>().parse("12,3"), Ok((12, ",3")));
+        assert_eq!(signed::<i32>().parse("-12"), Ok((-12, "")));
+        assert_eq!(signed::<i32>().parse("+7x"), Ok((7, "x")));
+        let error = unsigned::<u32>().parse("-1").unwrap_err();
+        assert_eq!((error.found, error.expected.as_str()), ("-", "a number"));
+        let error = unsigned::<u8>().parse("300").unwrap_err();
+        assert_eq!(error.found, "300");
+    }
+
+    #[test]
+    pub fn combinators() {
+        let list = delimited(
+            token("("),
+            separated(unsigned::<u32>(), token(",")),
+            token(")"),
+        );
+        assert_eq!(list.parse("(1,2,3) x"), Ok((vec![1, 2, 3], " x")));
+        let lists = many(terminated(&list, token(" ")));
+        assert_eq!(
+            lists.parse("(1) (2,3) {"),
+            Ok((vec![vec![1], vec![2, 3]], "{"))
+        );
+        let error = lists.parse("(1) (2,x) {").unwrap_err();
+        assert_eq!((error.found, error.expected.as_str()), ("x", "a number"));
+        let light = alt(map(token("."), |_| false), map(token("#"), |_| true));
+        assert_eq!(many(&light).parse(".#!"), Ok((vec![false, true], "!")));
+        assert_eq!(opt(&light).parse("x"), Ok((None, "x")));
+        let error = light.parse("x").unwrap_err();
+        assert_eq!(error.expected, "'.' or '#'");
+        let three = try_map(separated(unsigned::<u32>(), token(" ")), |numbers| {
+            <[u32; 3]>::try_from(numbers).map_err(|_| "3 numbers")
+        });
+        assert_eq!(three.parse("1 2 3"), Ok(([1, 2, 3], "")));
+        assert_eq!(three.parse("1 2").unwrap_err().found, "1 2");
+    }
+
+    #[test]
+    pub fn whole_input() {
+        let input = "1,2\n3,x";
+        let line = &input[4..];
+        let numbers = separated(unsigned::<u32>(), token(","));
+        assert_eq!(parse_all(input, &input[..3], &numbers), Ok(vec![1, 2]));
+        let error = parse_all(input, line, &numbers).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number, found \"x\""
+        );
+        let error = parse_all(input, line, unsigned::<u32>()).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected the end, found \",x\""
+        );
+    }
+
+    #[test]
+    pub fn scan() {
+        let input = "12x5: 1 0 1\n3-x\n7,1";
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(
+            scan!(input, lines[0], "{}x{}: {}", usize, u32, &str),
+            Ok((12, 5, "1 0 1"))
+        );
+        let error = scan!(input, lines[1], "{}-{}", u64, u64).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 3: expected a number, found \"x\""
+        );
+        let error = scan!(input, lines[2], "{}-{}", u64, u64).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 1: expected \"-\", found \"7,1\""
+        );
+        let error = scan!(input, lines[2], "({},{})", char, char).unwrap_err();
+        assert_eq!(error.expected, "\"({},{})\"");
+        let error = scan!(input, lines[0], "{}x{}:", u8, String).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1, column 6: expected the end, found \" 1 0 1\""
+        );
+    }
+
+    #[test]
+    pub fn blank_line_sections() {
+        let input = "0:\n#.\n\n\n1:\n.#\n \n4x4: 1 0\n";
+        let mut sections = sections(input);
+        let first = sections.next_section("present 0").unwrap();
+        assert_eq!((first.line, first.text), (1, "0:\n#."));
+        assert_eq!(
+            first.header(input, "0:").unwrap().collect::<Vec<_>>(),
+            ["#."]
+        );
+        let second = sections.next_section("present 1").unwrap();
+        assert_eq!((second.line, second.text), (5, "1:\n.#"));
+        let error = second.header(input, "2:").unwrap_err();
+        assert_eq!((error.line, error.text.as_str()), (5, "1:"));
+        assert_eq!(ParseError::at(input, second.end(), "").column, 3);
+        assert_eq!(sections.next().map(|section| section.line), Some(8));
+        assert!(sections.finish().is_ok());
+        let mut rest = super::sections(input);
+        rest.next();
+        let error = rest.finish().unwrap_err();
+        assert_eq!(
+            (error.line, error.expected.as_str()),
+            (5, "the end of the input")
+        );
+        let error = super::sections("\n").next_section("ranges").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 1: expected ranges, found nothing"
+        );
+    }
+
+    #[test]
+    pub fn fixed_width_table() {
+        use Alignment::*;
+        let input = "123 328  51 64    \n 45 64  387 23\n  6 98  215 314\n*   +   *   +  ";
+        let table = Table::new(input.lines().collect());
+        assert_eq!(table.columns(), [0..3, 4..7, 8..11, 12..15]);
+        let rows: Vec<Vec<&str>> = table
+            .rows()
+            .map(|row| row.iter().map(|cell| cell.text).collect())
+            .collect();
+        assert_eq!(rows[1], [" 45", "64 ", "387", "23"]);
+        let column = table.column(3);
+        let alignments = column.iter().map(Cell::alignment).collect::<Vec<_>>();
+        assert_eq!(alignments, [Left, Left, Full, Left]);
+        assert_eq!(table.cell(2, 0).alignment(), Right);
+        let padded: Vec<String> = column.iter().map(|cell| cell.chars().collect()).collect();
+        assert_eq!(padded, ["64 ", "23 ", "314", "+  "]);
+    }
+
+    #[test]
+    pub fn ragged_table() {
+        let input = "é  x\n é\n";
+        let table = Table::new(input.lines().collect());
+        assert_eq!(table.columns(), [0..2, 3..4]);
+        assert_eq!(table.cell(1, 0).text, " é");
+        assert_eq!(table.cell(1, 1), Cell { text: "", width: 1 });
+        assert_eq!(table.cell(1, 1).alignment(), Alignment::Empty);
+        let error = ParseError::at(input, table.cell(1, 1).text, "x");
+        assert_eq!((error.line, error.column), (2, 3));
+    }
+
+    #[derive(Debug, PartialEq, PuzzleParse)]
+    #[puzzle(separator = ",")]
+    struct Position {
+        x: i64,
+        y: i64,
+    }
+
+    #[derive(Debug, PartialEq, PuzzleParse)]
+    struct Machine<'a> {
+        #[puzzle(delimiters = "[]")]
+        lights: &'a str,
+        #[puzzle(delimiters = "()", separator = ",", repeated)]
+        buttons: Vec<Vec<usize>>,
+        #[puzzle(delimiters = "{}", separator = ",")]
+        joltages: Vec<u32>,
+    }
+
+    #[test]
+    pub fn derived_parsers() {
+        let input = "7,-1\n[.##.] (3) (1,3) {3,5,4,7}\n[#] {1}\n[#] (0,x) {1}\n7,1,2";
+        let lines: Vec<&str> = input.lines().collect();
+        assert_eq!(
+            Position::read(input, lines[0]),
+            Ok(Position { x: 7, y: -1 })
+        );
+        let machine = Machine {
+            lights: ".##.",
+            buttons: vec![vec![3], vec![1, 3]],
+            joltages: vec![3, 5, 4, 7],
+        };
+        assert_eq!(Machine::read(input, lines[1]), Ok(machine));
+        assert_eq!(Machine::read(input, lines[2]).unwrap().buttons.len(), 0);
+        let error = Machine::read(input, lines[3]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 8: expected a number for `buttons`, found \"x\""
+        );
+        let error = Position::read(input, lines[4]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 5, column 3: expected a number for `y`, found \"1,2\""
+        );
+    }
+
+    #[derive(Debug, PartialEq, PuzzleParse)]
+    struct Shadowing<'a> {
+        input: u32,
+        #[puzzle(delimiters = "()")]
+        text: &'a str,
+        #[puzzle(delimiters = "[]", separator = ",", repeated)]
+        rest: Vec<Vec<u32>>,
+    }
+
+    #[test]
+    pub fn derived_fields_named_like_locals() {
+        let input = "1 (a b) [2,3] [4]";
+        let parsed = Shadowing {
+            input: 1,
+            text: "a b",
+            rest: vec![vec![2, 3], vec![4]],
+        };
+        assert_eq!(Shadowing::read(input, input), Ok(parsed));
+    }
+
+    #[test]
+    pub fn byte_cursor() {
+        let input = "11-22,-7\n987\r\n12x\n€";
+        let mut cursor = Cursor::new(input);
+        assert_eq!(cursor.unsigned(), Ok(11));
+        cursor.expect(b'-').unwrap();
+        assert_eq!(cursor.unsigned(), Ok(22));
+        cursor.skip_all(b",");
+        assert_eq!(cursor.signed(), Ok(-7));
+        assert!(cursor.skip(b'\n'));
+        assert_eq!(cursor.digit_row(), Ok(vec![9, 8, 7]));
+        let position = cursor.position;
+        let error = cursor.digit_row().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 3, column 3: expected a digit, found \"x\""
+        );
+        assert_eq!(cursor.position, position);
+        assert_eq!(cursor.line(), Some(&b"12x"[..]));
+        let error = cursor.unsigned().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 1: expected a number, found \"€\""
+        );
+
+        let mut lines = Cursor::new("a\r\n\nb");
+        let lines: Vec<&[u8]> = std::iter::from_fn(|| lines.line()).collect();
+        assert_eq!(lines, [&b"a"[..], b"", b"b"]);
+        let error = Cursor::new("99999999999999999999").unsigned().unwrap_err();
+        assert_eq!(error.expected, "a smaller number");
+        assert_eq!(Cursor::new("-9223372036854775808").signed(), Ok(i64::MIN));
+        assert!(Cursor::new("9223372036854775808").signed().is_err());
+    }
+
+    #[test]
+    pub fn renders_diagnostics() {
+        let input = "0:\n#x#\n\tab 12\n";
+        let error = ParseError::at(input, &input[4..5], "'.' or '#'").context("present 0");
+        assert_eq!(
+            String::from(error),
+            "line 2, column 2: expected '.' or '#' in present 0, found \"x\"\n  |\n2 | #x#\n  |  ^"
+        );
+        let error = ParseError::at(input, &input[11..13], "a letter");
+        assert_eq!(error.render().lines().last(), Some("  | \t   ^^"));
+        let error = ParseError::end(input, "a present");
+        assert_eq!(
+            error.render().lines().skip(2).collect::<Vec<_>>(),
+            ["4 | ", "  | ^"]
+        );
+    }
+}
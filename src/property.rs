@@ -0,0 +1,166 @@
+use crate::random::Rng;
+use std::env;
+use std::fmt::Debug;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+/// How many random cases to try and which seed to start from. Both can be
+/// overridden with the `AOC_PROPERTY_CASES` and `AOC_PROPERTY_SEED`
+/// environment variables, to reproduce a failure or to search longer.
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            cases: from_env("AOC_PROPERTY_CASES").unwrap_or(200),
+            seed: from_env("AOC_PROPERTY_SEED").unwrap_or(2025),
+        }
+    }
+}
+
+fn from_env<T: FromStr>(name: &str) -> Option<T> {
+    env::var(name).ok().and_then(|value| value.parse().ok())
+}
+
+#[derive(Debug)]
+pub struct Failure<T> {
+    pub seed: u64,
+    pub case: usize,
+    pub original: T,
+    pub shrunk: T,
+    pub shrink_steps: usize,
+}
+
+/// Checks `property` on `config.cases` generated values. A property fails by
+/// returning false or by panicking. The first failing value is shrunk by
+/// repeatedly moving to the first smaller candidate that still fails.
+pub fn check<T: Clone>(
+    config: &Config,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) -> Result<(), Failure<T>> {
+    let holds =
+        |value: &T| panic::catch_unwind(AssertUnwindSafe(|| property(value))).unwrap_or(false);
+    let mut rng = Rng::new(config.seed);
+    for case in 0..config.cases {
+        let original = generate(&mut rng);
+        if holds(&original) {
+            continue;
+        }
+        let mut shrunk = original.clone();
+        let mut shrink_steps = 0;
+        while let Some(smaller) = shrink(&shrunk).into_iter().find(|s| !holds(s)) {
+            shrunk = smaller;
+            shrink_steps += 1;
+        }
+        return Err(Failure {
+            seed: config.seed,
+            case,
+            original,
+            shrunk,
+            shrink_steps,
+        });
+    }
+    Ok(())
+}
+
+/// Like `check` with the default configuration, but panics with the shrunk
+/// value when the property fails. Meant to be called from tests.
+pub fn assert_property<T: Clone + Debug>(
+    name: &str,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    property: impl Fn(&T) -> bool,
+) {
+    let config = Config::default();
+    if let Err(failure) = check(&config, generate, shrink, property) {
+        panic!(
+            "Property {name} failed for case {} with seed {} (shrunk in {} steps)\n\
+             minimal input: {:?}\noriginal input: {:?}",
+            failure.case, failure.seed, failure.shrink_steps, failure.shrunk, failure.original
+        );
+    }
+}
+
+/// Smaller versions of a vector: without a half, without one element, or
+/// with one element shrunk.
+pub fn shrink_vec<T: Clone>(vec: &[T], shrink_element: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+    if vec.len() > 1 {
+        candidates.push(vec[vec.len() / 2..].to_vec());
+        candidates.push(vec[..vec.len() / 2].to_vec());
+    }
+    for i in 0..vec.len() {
+        let mut removed = vec.to_vec();
+        removed.remove(i);
+        candidates.push(removed);
+    }
+    for (i, element) in vec.iter().enumerate() {
+        for smaller in shrink_element(element) {
+            let mut shrunk = vec.to_vec();
+            shrunk[i] = smaller;
+            candidates.push(shrunk);
+        }
+    }
+    candidates
+}
+
+/// Smaller numbers down to `min`: the minimum itself, halfway there and one
+/// less.
+pub fn shrink_number(n: u64, min: u64) -> Vec<u64> {
+    if n <= min {
+        return Vec::new();
+    }
+    let mut candidates = vec![min, min + (n - min) / 2, n - 1];
+    candidates.dedup();
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn shrinks_to_minimal_failure() {
+        let config = Config {
+            cases: 100,
+            seed: 1,
+        };
+        let failure = check(
+            &config,
+            |rng| (0..10).map(|_| rng.range(0..=100u64)).collect::<Vec<_>>(),
+            |vec| shrink_vec(vec, |n| shrink_number(*n, 0)),
+            |vec| vec.iter().all(|n| *n < 50),
+        )
+        .unwrap_err();
+        assert_eq!(failure.shrunk, vec![50]);
+    }
+
+    #[test]
+    pub fn panics_are_failures() {
+        let config = Config { cases: 10, seed: 1 };
+        let result = check(
+            &config,
+            |rng| rng.range(0..=10u64),
+            |_| Vec::new(),
+            |_| panic!(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    pub fn passing_property() {
+        let config = Config { cases: 10, seed: 1 };
+        let result = check(
+            &config,
+            |rng| rng.range(0..=10u64),
+            |_| Vec::new(),
+            |n| *n <= 10,
+        );
+        assert!(result.is_ok());
+    }
+}
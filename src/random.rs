@@ -0,0 +1,78 @@
+use std::ops::RangeInclusive;
+
+/// A small seeded pseudo-random number generator (SplitMix64). Good enough to
+/// generate puzzle inputs, and the same seed always gives the same input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in the range, for any non-negative integer type.
+    pub fn range<T>(&mut self, range: RangeInclusive<T>) -> T
+    where
+        T: Copy + TryInto<u64> + TryFrom<u64>,
+    {
+        let to_u64 = |x: T| {
+            x.try_into()
+                .unwrap_or_else(|_| panic!("Range must be non-negative"))
+        };
+        let (low, high) = (to_u64(*range.start()), to_u64(*range.end()));
+        assert!(low <= high, "Range cannot be empty");
+        let value = match (high - low).checked_add(1) {
+            Some(span) => low + self.next_u64() % span,
+            None => self.next_u64(),
+        };
+        T::try_from(value).unwrap_or_else(|_| unreachable!())
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0..=len - 1)
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn ranges() {
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            let x = rng.range(3..=5usize);
+            assert!((3..=5).contains(&x));
+        }
+        assert_eq!(rng.range(7..=7u32), 7);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    pub fn seeded() {
+        let mut rng1 = Rng::new(42);
+        let mut rng2 = Rng::new(42);
+        assert!((0..100).all(|_| rng1.next_u64() == rng2.next_u64()));
+    }
+}
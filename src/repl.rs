@@ -0,0 +1,200 @@
+use crate::input::{read_input, read_test_input};
+use crate::runner::{Params, Solution, catch, find_solution, solutions};
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+Commands:
+  day <day> [year]      select a day (defaults to the latest year)
+  paste                 enter input, finished by a line containing only '.'
+  load test|input       load the example or personal input of the selected day
+  load <path>           load input from a file
+  set <name> <value>    set a parameter, e.g. `set connections 10` for day 8
+  unset <name>          reset a parameter to its default
+  params                show the parameters that are set
+  run                   rerun the selected day on the current input
+  parse                 only show the parsed input
+  help                  show this message
+  quit                  leave the repl";
+
+struct Session {
+    solution: Option<Solution>,
+    input: Option<String>,
+    params: Params,
+}
+
+pub fn run(input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = Session {
+        solution: None,
+        input: None,
+        params: Params::default(),
+    };
+    let mut lines = input.lines();
+    writeln!(output, "Type `help` for a list of commands.")?;
+    loop {
+        write!(output, "> ")?;
+        output.flush()?;
+        let Some(line) = lines.next() else {
+            return Ok(());
+        };
+        let line = line?;
+        let words: Vec<_> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit" | "exit"] => return Ok(()),
+            ["help"] => writeln!(output, "{HELP}")?,
+            ["day", day] => select_day(&mut session, &mut output, latest_year(), day)?,
+            ["day", day, year] => match year.parse() {
+                Ok(year) => select_day(&mut session, &mut output, year, day)?,
+                Err(_) => writeln!(output, "Invalid year: {year}")?,
+            },
+            ["paste"] => {
+                let mut pasted = Vec::new();
+                for line in lines.by_ref() {
+                    let line = line?;
+                    if line == "." {
+                        break;
+                    }
+                    pasted.push(line);
+                }
+                session.input = Some(pasted.join("\n"));
+                evaluate(&session, &mut output, true)?;
+            }
+            ["load", source] => {
+                load(&mut session, &mut output, source)?;
+                evaluate(&session, &mut output, true)?;
+            }
+            ["set", name, value] => {
+                session.params.set(name, value);
+                evaluate(&session, &mut output, false)?;
+            }
+            ["unset", name] => {
+                session.params.unset(name);
+                evaluate(&session, &mut output, false)?;
+            }
+            ["params"] => {
+                for (name, value) in session.params.iter() {
+                    writeln!(output, "{name} = {value}")?;
+                }
+            }
+            ["run"] => evaluate(&session, &mut output, true)?,
+            ["parse"] => show_parsed(&session, &mut output)?,
+            _ => writeln!(
+                output,
+                "Unknown command: {line}. Type `help` for a list of commands."
+            )?,
+        }
+    }
+}
+
+fn latest_year() -> i32 {
+    solutions().iter().map(|s| s.year).max().unwrap_or(2025)
+}
+
+fn select_day(
+    session: &mut Session,
+    output: &mut impl Write,
+    year: i32,
+    day: &str,
+) -> io::Result<()> {
+    let solution = day.parse().ok().and_then(|day| find_solution(year, day));
+    match solution {
+        Some(solution) => {
+            writeln!(output, "Selected {} day {}.", solution.year, solution.day)?;
+            session.solution = Some(solution);
+            evaluate(session, output, false)
+        }
+        None => writeln!(output, "No solution for {year} day {day}."),
+    }
+}
+
+fn load(session: &mut Session, output: &mut impl Write, source: &str) -> io::Result<()> {
+    let loaded = match (source, &session.solution) {
+        ("test" | "input", None) => Err("Select a day first.".to_string()),
+        ("test", Some(s)) => read_test_input(s.year, s.day),
+        ("input", Some(s)) => read_input(s.year, s.day),
+        (path, _) => fs::read_to_string(path).map_err(|e| format!("Cannot read {path}: {e}")),
+    };
+    match loaded {
+        Ok(input) => session.input = Some(input),
+        Err(message) => writeln!(output, "{message}")?,
+    }
+    Ok(())
+}
+
+fn show_parsed(session: &Session, output: &mut impl Write) -> io::Result<()> {
+    let (Some(solution), Some(input)) = (&session.solution, &session.input) else {
+        return writeln!(output, "Select a day and paste or load an input first.");
+    };
+    match catch(|| (solution.parse)(input)) {
+        Ok(Ok(parsed)) => writeln!(output, "Parsed input:\n{parsed}"),
+        Ok(Err(message)) => writeln!(output, "Parsing failed: {message}"),
+        Err(message) => writeln!(output, "Parsing panicked: {message}"),
+    }
+}
+
+/// Shows the parsed input and the answer to every part. When `verbose` is
+/// false nothing is printed unless both a day and an input are present.
+fn evaluate(session: &Session, output: &mut impl Write, verbose: bool) -> io::Result<()> {
+    let (Some(solution), Some(input)) = (&session.solution, &session.input) else {
+        if verbose {
+            writeln!(output, "Select a day and paste or load an input first.")?;
+        }
+        return Ok(());
+    };
+    show_parsed(session, output)?;
+    for part in &solution.parts {
+        let name = match part.name {
+            "main" => format!("Part {}", part.part),
+            name => format!("Part {} ({name})", part.part),
+        };
+        match catch(|| (part.run)(input, &session.params)) {
+            Ok(Ok(answer)) => writeln!(output, "{name}: {answer}")?,
+            Ok(Err(message)) => writeln!(output, "{name} failed: {message}")?,
+            Err(message) => writeln!(output, "{name} panicked: {message}")?,
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(commands: &str) -> String {
+        let mut output = Vec::new();
+        run(commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    pub fn paste_and_solve() {
+        let output = session("day 1\npaste\nL68\nL30\nR48\n.\nquit\n");
+        assert!(output.contains("Selected 2025 day 1."));
+        assert!(output.contains("Part 1: 1"));
+        assert!(output.contains("Part 2: 2"));
+    }
+
+    #[test]
+    pub fn rerun_with_parameters() {
+        let output = session("day 8\npaste\n0,0,0\n0,0,1\n5,5,5\n9,9,9\n.\nset connections 1\n");
+        assert!(output.contains("Part 1: 2"));
+        assert!(output.contains("Part 1: 0"));
+    }
+
+    #[test]
+    pub fn errors_are_reported() {
+        let output = session("day 1\npaste\nL3\nX12\n.\n");
+        assert!(
+            output.contains("Parsing failed: line 2, column 1: expected 'L' or 'R', found \"X\"")
+        );
+    }
+
+    #[test]
+    pub fn unsolvable_inputs_are_reported() {
+        let output = session("day 8\npaste\n1,2,3\n.\n");
+        assert!(output.contains(
+            "Part 2 failed: no solution: the junction boxes never form a single circuit"
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::history::Trend;
+use crate::runner::DayRun;
+use std::fmt::Write;
+use std::time::Duration;
+
+const STYLE: &str = "
+body { font-family: sans-serif; background: #0f0f23; color: #cccccc; margin: 2em auto; max-width: 60em; }
+h1, h2 { color: #00cc00; }
+table { border-collapse: collapse; }
+td, th { padding: 0.2em 1em; text-align: left; }
+td.time { text-align: right; font-family: monospace; }
+.error { color: #ff6666; white-space: pre-wrap; font-family: monospace; }
+pre { background: #10101a; border: 1px solid #333340; padding: 0.5em; overflow-x: auto; line-height: 1; }
+polyline { fill: none; stroke: #ffff66; stroke-width: 1.5; }
+";
+
+/// A self-contained HTML page with the answers and timings of a run of the
+/// whole year, the timing history of each part and the visualizations of the
+/// days that have one.
+pub fn render(year: i32, commit: &str, runs: &[DayRun], trends: &[Trend]) -> String {
+    let mut html = String::new();
+    let title = format!("Advent of Code {year}");
+    write!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<p>Commit <code>{}</code></p>\n",
+        escape(commit)
+    )
+    .unwrap();
+    for run in runs {
+        let (year, day) = (run.solution.year, run.solution.day);
+        writeln!(html, "<section>\n<h2>Day {day}</h2>").unwrap();
+        let input = match &run.input {
+            Ok(input) => input,
+            Err(e) => {
+                writeln!(html, "<p class=\"error\">{}</p>\n</section>", escape(e)).unwrap();
+                continue;
+            }
+        };
+        html += "<table>\n<tr><th>Part</th><th>Answer</th><th>Time</th><th>History</th></tr>\n";
+        for timing in &run.timings {
+            let answer = match &timing.answer {
+                Ok(answer) => format!("<code>{}</code>", escape(answer)),
+                Err(e) => format!("<span class=\"error\">{}</span>", escape(e)),
+            };
+            let history: Vec<Duration> = trends
+                .iter()
+                .find(|t| {
+                    (t.year, t.day, t.part, t.name.as_str()) == (year, day, timing.part, "main")
+                })
+                .map(|t| t.commits.iter().map(|(_, time)| *time).collect())
+                .unwrap_or_default();
+            let time = if timing.cached {
+                "cached".to_string()
+            } else {
+                format!("{:.3?}", timing.time)
+            };
+            writeln!(
+                html,
+                "<tr><td>{}</td><td>{answer}</td><td class=\"time\">{time}</td><td>{}</td></tr>",
+                timing.part,
+                sparkline(&history)
+            )
+            .unwrap();
+        }
+        html += "</table>\n";
+        if let Some(visualize) = run.solution.visualize {
+            let picture = visualize(input).map_err(|e| format!("Cannot visualize: {e}"));
+            match picture {
+                Ok(picture) => writeln!(html, "<pre>{}</pre>", escape(&picture)).unwrap(),
+                Err(e) => writeln!(html, "<p class=\"error\">{}</p>", escape(&e)).unwrap(),
+            }
+        }
+        html += "</section>\n";
+    }
+    html += "</body>\n</html>\n";
+    html
+}
+
+/// An inline SVG line of the times, oldest on the left, scaled between the
+/// fastest and the slowest.
+fn sparkline(times: &[Duration]) -> String {
+    let (width, height) = (120.0, 24.0);
+    let Some(slowest) = times.iter().max() else {
+        return String::new();
+    };
+    let fastest = times.iter().min().unwrap();
+    let range = (*slowest - *fastest).as_secs_f64();
+    let step = width / (times.len().max(2) - 1) as f64;
+    let points: Vec<String> = times
+        .iter()
+        .enumerate()
+        .map(|(i, time)| {
+            let scaled = if range == 0.0 {
+                0.5
+            } else {
+                (*time - *fastest).as_secs_f64() / range
+            };
+            format!(
+                "{:.1},{:.1}",
+                i as f64 * step,
+                2.0 + (height - 4.0) * (1.0 - scaled)
+            )
+        })
+        .collect();
+    let title: Vec<String> = times.iter().map(|time| format!("{time:.1?}")).collect();
+    format!(
+        "<svg width=\"{width}\" height=\"{height}\"><title>{}</title>\
+         <polyline points=\"{}\"/></svg>",
+        escape(&title.join(" → ")),
+        points.join(" ")
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::{Solution, Timing};
+
+    #[test]
+    pub fn renders_self_contained_page() {
+        let solution = Solution {
+            year: 2025,
+            day: 4,
+            parse: |input| Ok(input.to_string()),
+            generate: |_, _| String::new(),
+            visualize: Some(|input| Ok(input.replace('@', "<x>"))),
+            parts: Vec::new(),
+            examples: Vec::new(),
+        };
+        let timing = |part, answer: Result<&str, &str>| Timing {
+            part,
+            name: "main",
+            answer: answer.map(str::to_string).map_err(str::to_string),
+            time: Duration::from_millis(3),
+            cached: false,
+        };
+        let runs = vec![DayRun {
+            solution: &solution,
+            input: Ok("@.@".to_string()),
+            timings: vec![timing(1, Ok("13")), timing(2, Err("No solution"))],
+        }];
+        let trends = vec![Trend {
+            year: 2025,
+            day: 4,
+            part: 1,
+            name: "main".to_string(),
+            commits: vec![
+                ("a".to_string(), Duration::from_millis(5)),
+                ("b".to_string(), Duration::from_millis(3)),
+            ],
+        }];
+        let html = render(2025, "abc", &runs, &trends);
+        assert!(html.contains("<code>13</code>"));
+        assert!(html.contains("No solution"));
+        assert!(html.contains("<pre>&lt;x&gt;.&lt;x&gt;</pre>"));
+        assert!(html.contains("<polyline points=\"0.0,2.0 120.0,22.0\"/>"));
+        assert!(!html.contains("src=") && !html.contains("href="));
+    }
+}
@@ -0,0 +1,400 @@
+use crate::cache::Cache;
+use crate::input::{open_input, open_test_input, read_input, read_test_input};
+use crate::random::Rng;
+use crate::year2025;
+use itertools::Itertools;
+use std::any::Any;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::BufRead;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Draws the solved puzzle for an input as text.
+pub type Visualize = fn(&str) -> Result<String, String>;
+
+/// Solves a part reading its input a piece at a time, for inputs too large to
+/// hold in memory.
+pub type Stream = fn(&mut dyn BufRead, &Params) -> Result<String, String>;
+
+/// A registered puzzle solution: how to parse the input of one day, how to
+/// solve each of its parts and how to generate random inputs of a given size.
+pub struct Solution {
+    pub year: i32,
+    pub day: i32,
+    pub parse: fn(&str) -> Result<String, String>,
+    pub generate: fn(&mut Rng, usize) -> String,
+    /// A text picture of the solved puzzle, for the days that have one.
+    pub visualize: Option<Visualize>,
+    pub parts: Vec<Part>,
+    /// The answers given in the puzzle text for its example inputs.
+    pub examples: Vec<Example>,
+}
+
+impl Solution {
+    /// The part numbers in the order they are registered.
+    pub fn part_numbers(&self) -> Vec<i32> {
+        self.parts.iter().map(|p| p.part).unique().collect()
+    }
+
+    /// The main implementation of every part, skipping the alternatives.
+    pub fn main_parts(&self) -> impl Iterator<Item = &Part> {
+        self.part_numbers()
+            .into_iter()
+            .filter_map(|number| self.implementations(number).next())
+    }
+
+    pub fn implementations(&self, part: i32) -> impl Iterator<Item = &Part> {
+        self.parts.iter().filter(move |p| p.part == part)
+    }
+}
+
+/// One implementation of a part. A part can have several implementations,
+/// the first one registered is the main one.
+pub struct Part {
+    pub part: i32,
+    pub name: &'static str,
+    pub run: fn(&str, &Params) -> Result<String, String>,
+    /// The same part reading a streamed input, for the days that can.
+    pub stream: Option<Stream>,
+}
+
+impl Part {
+    pub fn new(part: i32, run: fn(&str, &Params) -> Result<String, String>) -> Part {
+        Part::alternative(part, "main", run)
+    }
+
+    pub fn alternative(
+        part: i32,
+        name: &'static str,
+        run: fn(&str, &Params) -> Result<String, String>,
+    ) -> Part {
+        Part {
+            part,
+            name,
+            run,
+            stream: None,
+        }
+    }
+
+    pub fn streamed(self, stream: Stream) -> Part {
+        Part {
+            stream: Some(stream),
+            ..self
+        }
+    }
+}
+
+/// The expected answer of a part on an example input.
+pub struct Example {
+    pub part: i32,
+    /// The number of the test input file when the part has its own example,
+    /// otherwise the day's `test<day>.txt` is used.
+    pub test_input: Option<i32>,
+    pub params: Params,
+    pub answer: &'static str,
+}
+
+impl Example {
+    pub fn new(part: i32, answer: &'static str) -> Example {
+        Example {
+            part,
+            test_input: None,
+            params: Params::default(),
+            answer,
+        }
+    }
+
+    pub fn with_param(mut self, name: &str, value: &str) -> Example {
+        self.params.set(name, value);
+        self
+    }
+
+    pub fn with_test_input(mut self, test_input: i32) -> Example {
+        self.test_input = Some(test_input);
+        self
+    }
+}
+
+/// Named parameters for parts that take more than the input, such as the
+/// number of connections for day 8, which differs between example and input.
+#[derive(Clone, Debug, Default)]
+pub struct Params(HashMap<String, String>);
+
+impl Params {
+    pub fn get<T: FromStr>(&self, name: &str, default: T) -> T {
+        self.0
+            .get(name)
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(default)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.0.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn unset(&mut self, name: &str) {
+        self.0.remove(name);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.0.iter()
+    }
+}
+
+pub fn solutions() -> Vec<Solution> {
+    year2025::solutions()
+}
+
+pub fn find_solution(year: i32, day: i32) -> Option<Solution> {
+    solutions()
+        .into_iter()
+        .find(|solution| solution.year == year && solution.day == day)
+}
+
+/// Why a part gave up on an input that parsed fine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    /// The input needs something the solution does not implement.
+    Unsupported(String),
+    /// The input has no answer.
+    NoSolution(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Unsupported(reason) => write!(f, "unsupported input: {reason}"),
+            SolveError::NoSolution(reason) => write!(f, "no solution: {reason}"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<SolveError> for String {
+    fn from(error: SolveError) -> String {
+        error.to_string()
+    }
+}
+
+pub struct Timing {
+    pub part: i32,
+    pub name: &'static str,
+    pub answer: Result<String, String>,
+    pub time: Duration,
+    /// The answer came from the cache, so the time means nothing.
+    pub cached: bool,
+}
+
+pub struct Comparison {
+    pub part: i32,
+    /// Every implementation of the part, fastest first.
+    pub timings: Vec<Timing>,
+}
+
+impl Comparison {
+    pub fn agrees(&self) -> bool {
+        self.timings.iter().map(|t| &t.answer).all_equal()
+    }
+}
+
+/// Runs and times a part. A panic is reported as an error so that the other
+/// parts and days still run.
+pub fn time_part(part: &Part, input: &str, params: &Params) -> Timing {
+    let start = Instant::now();
+    let answer = catch(|| (part.run)(input, params))
+        .unwrap_or_else(|message| Err(format!("panicked: {message}")));
+    Timing {
+        part: part.part,
+        name: part.name,
+        answer,
+        time: start.elapsed(),
+        cached: false,
+    }
+}
+
+/// Runs and times a part on the day's input streamed from its file, or on its
+/// example with `test`. Parts that cannot stream their input are errors.
+pub fn time_streamed_part(solution: &Solution, part: &Part, test: bool, params: &Params) -> Timing {
+    let start = Instant::now();
+    let answer = match part.stream {
+        Some(stream) => {
+            let reader = if test {
+                open_test_input(solution.year, solution.day)
+            } else {
+                open_input(solution.year, solution.day)
+            };
+            reader.and_then(|mut reader| {
+                catch(|| stream(&mut reader, params))
+                    .unwrap_or_else(|message| Err(format!("panicked: {message}")))
+            })
+        }
+        None => Err("this part cannot stream its input".to_string()),
+    };
+    Timing {
+        part: part.part,
+        name: part.name,
+        answer,
+        time: start.elapsed(),
+        cached: false,
+    }
+}
+
+/// Runs every implementation of every part on the same input.
+pub fn compare(solution: &Solution, input: &str, params: &Params) -> Vec<Comparison> {
+    solution
+        .part_numbers()
+        .into_iter()
+        .map(|part| {
+            let mut timings: Vec<_> = solution
+                .implementations(part)
+                .map(|implementation| time_part(implementation, input, params))
+                .collect();
+            timings.sort_by_key(|timing| timing.time);
+            Comparison { part, timings }
+        })
+        .collect()
+}
+
+/// The main implementation of every part of a day run on its input.
+pub struct DayRun<'a> {
+    pub solution: &'a Solution,
+    pub input: Result<String, String>,
+    /// Empty when the input could not be read.
+    pub timings: Vec<Timing>,
+}
+
+/// Runs every given day on its input, or on its example with `test`. With a
+/// cache, cached answers are used instead of running the part and new answers
+/// are added to it.
+pub fn run_all<'a>(
+    solutions: &'a [Solution],
+    test: bool,
+    params: &Params,
+    mut cache: Option<&mut Cache>,
+) -> Vec<DayRun<'a>> {
+    solutions
+        .iter()
+        .map(|solution| {
+            let input = if test {
+                read_test_input(solution.year, solution.day)
+            } else {
+                read_input(solution.year, solution.day)
+            };
+            let timings = match &input {
+                Ok(input) => solution
+                    .main_parts()
+                    .map(|part| run_cached(solution, part, input, params, cache.as_deref_mut()))
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
+            DayRun {
+                solution,
+                input,
+                timings,
+            }
+        })
+        .collect()
+}
+
+fn run_cached(
+    solution: &Solution,
+    part: &Part,
+    input: &str,
+    params: &Params,
+    cache: Option<&mut Cache>,
+) -> Timing {
+    let Some(cache) = cache else {
+        return time_part(part, input, params);
+    };
+    let (year, day) = (solution.year, solution.day);
+    if let Some(answer) = cache.get(year, day, part.part, input, params) {
+        return Timing {
+            part: part.part,
+            name: part.name,
+            answer: Ok(answer.to_string()),
+            time: Duration::ZERO,
+            cached: true,
+        };
+    }
+    let timing = time_part(part, input, params);
+    if let Ok(answer) = &timing.answer
+        && let Err(e) = cache.insert(year, day, part.part, input, params, answer)
+    {
+        eprintln!(
+            "Could not cache the answer of {year} day {day} part {}: {e}",
+            part.part
+        );
+    }
+    timing
+}
+
+/// Runs the main implementation of the example's part on its input. Fails
+/// when the input cannot be read or the part fails, and otherwise tells
+/// whether the answer is the expected one.
+pub fn check_example(solution: &Solution, example: &Example) -> Result<bool, String> {
+    let input = read_test_input(solution.year, example.test_input.unwrap_or(solution.day))?;
+    let part = solution
+        .implementations(example.part)
+        .next()
+        .ok_or_else(|| format!("There is no part {}", example.part))?;
+    let answer = catch(|| (part.run)(&input, &example.params))??;
+    Ok(answer == example.answer)
+}
+
+/// Runs `f`, turning a panic into an error with the panic message.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn compare_implementations() {
+        let solution = find_solution(2025, 2).unwrap();
+        let comparisons = compare(&solution, "11-22,95-115,998-1012", &Params::default());
+        assert_eq!(comparisons.len(), 2);
+        for comparison in comparisons {
+            assert!(comparison.timings.len() > 1);
+            assert!(comparison.agrees());
+        }
+    }
+
+    #[test]
+    pub fn panics_are_errors() {
+        let part = Part::new(1, |_, _| panic!("out of cheese"));
+        let timing = time_part(&part, "", &Params::default());
+        assert_eq!(timing.answer, Err("panicked: out of cheese".to_string()));
+        assert_eq!(
+            String::from(SolveError::NoSolution("no loop".to_string())),
+            "no solution: no loop"
+        );
+    }
+
+    #[test]
+    pub fn generated_inputs_are_solvable() {
+        let mut rng = Rng::new(2025);
+        for solution in solutions() {
+            let input = (solution.generate)(&mut rng, 10);
+            assert!((solution.parse)(&input).is_ok());
+            for part in solution.main_parts() {
+                assert!((part.run)(&input, &Params::default()).is_ok());
+            }
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use crate::cache::Cache;
+use crate::input::{has_input, read_answers, read_input, read_test_input};
+use crate::runner::{Params, Solution, check_example};
+use itertools::Itertools;
+
+const DAYS_PER_ROW: usize = 6;
+const CELL_WIDTH: usize = 9;
+
+#[derive(Debug, PartialEq)]
+pub enum ExampleStatus {
+    Passed,
+    Failed,
+    /// No example answer registered, or no example input on disk.
+    Missing,
+}
+
+/// How the answer recorded in `answers<day>.txt` compares with the answer
+/// the current build cached for the input.
+#[derive(Debug, PartialEq)]
+pub enum AnswerStatus {
+    Verified,
+    Wrong,
+    /// No answer recorded, or none cached by this build to compare with.
+    Unchecked,
+}
+
+#[derive(Debug)]
+pub struct PartStatus {
+    pub part: i32,
+    pub answer: AnswerStatus,
+    pub example: ExampleStatus,
+}
+
+#[derive(Debug)]
+pub struct DayStatus {
+    pub day: i32,
+    pub module: bool,
+    pub input: bool,
+    /// The implemented parts.
+    pub parts: Vec<PartStatus>,
+}
+
+/// The puzzles run from the 1st to the 25th, and up to the 12th since 2025.
+pub fn days_in_year(year: i32) -> i32 {
+    if year >= 2025 { 12 } else { 25 }
+}
+
+/// The state of every day of the year. Checking the examples runs them, but
+/// the real inputs are left alone so that this stays fast: their answers are
+/// only checked when `cache` has them from an earlier run.
+pub fn year_status(year: i32, solutions: &[Solution], cache: Option<&Cache>) -> Vec<DayStatus> {
+    (1..=days_in_year(year))
+        .map(|day| {
+            let solution = solutions.iter().find(|s| s.year == year && s.day == day);
+            let Some(solution) = solution else {
+                return DayStatus {
+                    day,
+                    module: false,
+                    input: has_input(year, day),
+                    parts: Vec::new(),
+                };
+            };
+            let answers = read_answers(year, day);
+            let input = read_input(year, day).ok();
+            let parts = solution
+                .part_numbers()
+                .into_iter()
+                .map(|part| {
+                    let computed = cache.zip(input.as_deref()).and_then(|(cache, input)| {
+                        cache.get(year, day, part, input, &Params::default())
+                    });
+                    let recorded = answers.get(part as usize - 1).map(String::as_str);
+                    PartStatus {
+                        part,
+                        answer: answer_status(recorded, computed),
+                        example: example_status(solution, part),
+                    }
+                })
+                .collect();
+            DayStatus {
+                day,
+                module: true,
+                input: has_input(year, day),
+                parts,
+            }
+        })
+        .collect()
+}
+
+fn answer_status(recorded: Option<&str>, computed: Option<&str>) -> AnswerStatus {
+    match (recorded.filter(|answer| !answer.is_empty()), computed) {
+        (Some(recorded), Some(computed)) if recorded == computed => AnswerStatus::Verified,
+        (Some(_), Some(_)) => AnswerStatus::Wrong,
+        _ => AnswerStatus::Unchecked,
+    }
+}
+
+fn example_status(solution: &Solution, part: i32) -> ExampleStatus {
+    let examples: Vec<_> = solution
+        .examples
+        .iter()
+        .filter(|e| e.part == part)
+        .filter(|e| read_test_input(solution.year, e.test_input.unwrap_or(solution.day)).is_ok())
+        .collect();
+    if examples.is_empty() {
+        ExampleStatus::Missing
+    } else if examples
+        .iter()
+        .all(|e| check_example(solution, e) == Ok(true))
+    {
+        ExampleStatus::Passed
+    } else {
+        ExampleStatus::Failed
+    }
+}
+
+/// An advent calendar of the year, one box per day with a star per part.
+pub fn render(year: i32, days: &[DayStatus]) -> String {
+    let mut lines = vec![format!("Advent of Code {year}")];
+    let border = |left: &str, middle: &str, right: &str, count: usize| {
+        let line = vec!["─".repeat(CELL_WIDTH); count].join(middle);
+        format!("{left}{line}{right}")
+    };
+    for chunk in days.chunks(DAYS_PER_ROW) {
+        lines.push(border("┌", "┬", "┐", chunk.len()));
+        let cells: Vec<[String; 3]> = chunk.iter().map(cell).collect();
+        for i in 0..3 {
+            let line = cells
+                .iter()
+                .map(|cell| format!("{:<CELL_WIDTH$}", cell[i]))
+                .join("│");
+            lines.push(format!("│{line}│"));
+        }
+        lines.push(border("└", "┴", "┘", chunk.len()));
+    }
+    lines.join("\n")
+        + "\n★ answer matches answers<day>.txt  ! answer differs  ☆ not checked  · no such part\n\
+           in: input present  ✓ example passes  ✗ example fails  ? no example\n"
+}
+
+fn cell(day: &DayStatus) -> [String; 3] {
+    let title = format!(" Day {:>2}", day.day);
+    let input = if day.input { "in" } else { "--" };
+    if !day.module {
+        return [title, "  --".to_string(), format!(" {input}")];
+    }
+    let last_part = day.parts.iter().map(|p| p.part).max().unwrap_or(0).max(2);
+    let (stars, examples): (String, String) = (1..=last_part)
+        .map(|part| match day.parts.iter().find(|p| p.part == part) {
+            None => ('·', ' '),
+            Some(status) => {
+                let star = match status.answer {
+                    AnswerStatus::Verified => '★',
+                    AnswerStatus::Wrong => '!',
+                    AnswerStatus::Unchecked => '☆',
+                };
+                let example = match status.example {
+                    ExampleStatus::Passed => '✓',
+                    ExampleStatus::Failed => '✗',
+                    ExampleStatus::Missing => '?',
+                };
+                (star, example)
+            }
+        })
+        .unzip();
+    [title, format!("  {stars}"), format!(" {input} {examples}")]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(part: i32, answer: AnswerStatus, example: ExampleStatus) -> PartStatus {
+        PartStatus {
+            part,
+            answer,
+            example,
+        }
+    }
+
+    #[test]
+    pub fn calendar() {
+        let days = vec![
+            DayStatus {
+                day: 1,
+                module: true,
+                input: true,
+                parts: vec![
+                    part(1, AnswerStatus::Verified, ExampleStatus::Passed),
+                    part(2, AnswerStatus::Wrong, ExampleStatus::Failed),
+                ],
+            },
+            DayStatus {
+                day: 12,
+                module: true,
+                input: false,
+                parts: vec![part(1, AnswerStatus::Unchecked, ExampleStatus::Missing)],
+            },
+            DayStatus {
+                day: 13,
+                module: false,
+                input: false,
+                parts: Vec::new(),
+            },
+        ];
+        let expected = "\
+Advent of Code 2025
+┌─────────┬─────────┬─────────┐
+│ Day  1  │ Day 12  │ Day 13  │
+│  ★!     │  ☆·     │  --     │
+│ in ✓✗   │ -- ?    │ --      │
+└─────────┴─────────┴─────────┘
+";
+        assert!(render(2025, &days).starts_with(expected));
+    }
+
+    #[test]
+    pub fn answers_are_compared() {
+        assert_eq!(
+            answer_status(Some("42"), Some("42")),
+            AnswerStatus::Verified
+        );
+        assert_eq!(answer_status(Some("42"), Some("41")), AnswerStatus::Wrong);
+        assert_eq!(answer_status(Some("42"), None), AnswerStatus::Unchecked);
+        assert_eq!(answer_status(Some(""), Some("")), AnswerStatus::Unchecked);
+        assert_eq!(answer_status(None, Some("42")), AnswerStatus::Unchecked);
+    }
+
+    #[test]
+    pub fn wraps_rows() {
+        let days: Vec<_> = (1..=7)
+            .map(|day| DayStatus {
+                day,
+                module: false,
+                input: false,
+                parts: Vec::new(),
+            })
+            .collect();
+        let calendar = render(2025, &days);
+        let tops: Vec<_> = calendar.lines().filter(|l| l.starts_with('┌')).collect();
+        assert_eq!(tops.len(), 2);
+        assert_eq!(tops[1].chars().count(), CELL_WIDTH + 2);
+    }
+}
@@ -10,3 +10,22 @@ pub mod day9;
 pub mod day10;
 pub mod day11;
 pub mod day12;
+
+use crate::runner::Solution;
+
+pub fn solutions() -> Vec<Solution> {
+    vec![
+        day1::solution(),
+        day2::solution(),
+        day3::solution(),
+        day4::solution(),
+        day5::solution(),
+        day6::solution(),
+        day7::solution(),
+        day8::solution(),
+        day9::solution(),
+        day10::solution(),
+        day11::solution(),
+        day12::solution(),
+    ]
+}
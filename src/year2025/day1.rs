@@ -1,16 +1,28 @@
-pub fn handle_input(input: &str) -> impl Iterator<Item = (i32, i32)> {
-    input.lines().map(parse_line)
+use crate::input::stream;
+use crate::parsers::{ParseError, char_at, parse_number};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
+use itertools::process_results;
+use std::io::BufRead;
+
+pub fn handle_input(input: &str) -> impl Iterator<Item = Result<(i32, i32), ParseError>> {
+    input.lines().map(|line| parse_line(input, line))
+}
+
+/// Reads the rotations one line at a time.
+pub fn stream_input(reader: impl BufRead) -> impl Iterator<Item = Result<(i32, i32), String>> {
+    stream(reader, b'\n', |line| parse_line(line, line))
 }
 
-fn parse_line(line: &str) -> (i32, i32) {
-    let first_letter = line.chars().nth(0);
-    let sign = match first_letter {
-        Some('L') => -1,
-        Some('R') => 1,
-        _ => panic!("Line doesn't start with L or R"),
+fn parse_line(input: &str, line: &str) -> Result<(i32, i32), ParseError> {
+    let direction = char_at(line, 0);
+    let sign = match direction {
+        "L" => -1,
+        "R" => 1,
+        _ => return Err(ParseError::at(input, direction, "'L' or 'R'")),
     };
-    let distance = line[1..].parse::<i32>().unwrap();
-    (sign, distance)
+    let distance = parse_number(input, &line[direction.len()..], "a distance")?;
+    Ok((sign, distance))
 }
 
 pub fn part1(rotations: impl Iterator<Item = (i32, i32)>) -> i32 {
@@ -43,24 +55,129 @@ pub fn part2(rotations: impl Iterator<Item = (i32, i32)>) -> i32 {
     zeros
 }
 
+/// Turns the dial one click at a time, as a slow but obviously correct
+/// reference for `part2`.
+pub fn part2_simulated(rotations: impl Iterator<Item = (i32, i32)>) -> i32 {
+    let mut zeros = 0;
+    let mut dial = 50;
+    for (dir, dist) in rotations {
+        for _ in 0..dist {
+            dial = (dial + dir).rem_euclid(100);
+            if dial == 0 {
+                zeros += 1;
+            }
+        }
+    }
+    zeros
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+            format!("{direction}{}\n", rng.range(1..=999))
+        })
+        .collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 1,
+        parse: |input| {
+            let rotations: Vec<_> = handle_input(input).collect::<Result<_, _>>()?;
+            Ok(format!("{rotations:#?}"))
+        },
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| {
+                Ok(process_results(handle_input(input), |rotations| {
+                    part1(rotations).to_string()
+                })?)
+            })
+            .streamed(|reader, _| {
+                process_results(stream_input(reader), |rotations| {
+                    part1(rotations).to_string()
+                })
+            }),
+            Part::new(2, |input, _| {
+                Ok(process_results(handle_input(input), |rotations| {
+                    part2(rotations).to_string()
+                })?)
+            })
+            .streamed(|reader, _| {
+                process_results(stream_input(reader), |rotations| {
+                    part2(rotations).to_string()
+                })
+            }),
+            Part::alternative(2, "simulated", |input, _| {
+                Ok(process_results(handle_input(input), |rotations| {
+                    part2_simulated(rotations).to_string()
+                })?)
+            }),
+        ],
+        examples: vec![Example::new(1, "3"), Example::new(2, "6")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::input::get_test_input;
+    use crate::property::{assert_property, shrink_number, shrink_vec};
 
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 1);
-        let input = handle_input(&input);
-        let res = super::part1(input);
+        let res = process_results(handle_input(&input), |input| super::part1(input)).unwrap();
         assert_eq!(res, 3);
     }
 
+    #[test]
+    pub fn streamed() {
+        let input = "L68\r\nL30\nR48\nL5\n";
+        let streamed: Vec<_> = stream_input(input.as_bytes()).map(Result::unwrap).collect();
+        assert_eq!(streamed, vec![(-1, 68), (-1, 30), (1, 48), (-1, 5)]);
+
+        let error = stream_input("L68\nL30\nX48\n".as_bytes())
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(
+            error,
+            "line 3, column 1: expected 'L' or 'R', found \"X\"\n  |\n3 | X48\n  | ^"
+        );
+    }
+
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 1);
-        let input = handle_input(&input);
-        let res = super::part2(input);
+        let res = process_results(handle_input(&input), |input| super::part2(input)).unwrap();
         assert_eq!(res, 6);
     }
+
+    #[test]
+    pub fn part2_matches_simulation() {
+        assert_property(
+            "day1::part2 matches a click-by-click simulation",
+            |rng| {
+                let rotations = rng.range(1..=20);
+                (0..rotations)
+                    .map(|_| (if rng.chance(0.5) { -1 } else { 1 }, rng.range(1..=300)))
+                    .collect::<Vec<(i32, i32)>>()
+            },
+            |rotations| {
+                shrink_vec(rotations, |(dir, dist)| {
+                    shrink_number(*dist as u64, 1)
+                        .into_iter()
+                        .map(|dist| (*dir, dist as i32))
+                        .collect()
+                })
+            },
+            |rotations| {
+                super::part2(rotations.iter().copied())
+                    == part2_simulated(rotations.iter().copied())
+            },
+        );
+    }
 }
@@ -1,10 +1,10 @@
-use crate::parsers::parse_with_delimiters;
+use crate::parsers::{ParseError, PuzzleParse, char_at, groups};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution, SolveError};
 use itertools::Either::{Left, Right};
 use itertools::{Either, Itertools};
 use std::fmt::Debug;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, Ordering};
-use std::thread;
+use std::{panic, thread};
 
 struct LightDiagram(Vec<bool>);
 
@@ -20,78 +20,80 @@ impl Debug for LightDiagram {
     }
 }
 
-#[derive(Debug)]
+impl PuzzleParse<'_> for LightDiagram {
+    fn read(input: &str, text: &str) -> Result<Self, ParseError> {
+        let lights = text
+            .char_indices()
+            .map(|(i, l)| match l {
+                '.' => Ok(false),
+                '#' => Ok(true),
+                _ => Err(ParseError::at(input, char_at(text, i), "'.' or '#'")),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(LightDiagram(lights))
+    }
+}
+
+#[derive(Debug, PuzzleParse)]
 pub struct Machine {
+    #[puzzle(delimiters = "[]")]
     light_diagram: LightDiagram,
+    #[puzzle(delimiters = "()", separator = ",", repeated)]
     button_wiring_schematics: Vec<Button>,
+    #[puzzle(delimiters = "{}", separator = ",")]
     joltage_requirements: Vec<i32>,
 }
 
 type Button = Vec<usize>;
 
-pub fn handle_input(input: &str) -> Vec<Machine> {
-    input.lines().map(parse_machine).collect()
+pub fn handle_input(input: &str) -> Result<Vec<Machine>, ParseError> {
+    input
+        .lines()
+        .map(|line| parse_machine(input, line))
+        .collect()
 }
 
-fn parse_machine(input: &str) -> Machine {
-    let words: Vec<_> = input.split_whitespace().collect();
-    let light_diagram = parse_light_diagram(words.first().expect("Line should have words"));
-    let button_wiring_schematics = words[1..words.len() - 1]
+fn parse_machine(input: &str, line: &str) -> Result<Machine, ParseError> {
+    let machine = Machine::read(input, line)?;
+    let lights = machine.light_diagram.0.len();
+    if let Some(i) = machine
+        .button_wiring_schematics
         .iter()
-        .map(|x| parse_button_wiring_schematic(*x))
-        .collect();
-    let joltage_requirements =
-        parse_joltage_requirements(words.last().expect("Line should have words"));
-    Machine {
-        light_diagram,
-        button_wiring_schematics,
-        joltage_requirements,
+        .position(|wiring| wiring.iter().any(|l| *l >= lights))
+    {
+        let button = groups(input, line, '(', ')')?[i];
+        let expected = format!("a button for lights below {lights}");
+        return Err(ParseError::at(input, button, expected));
     }
+    if machine.joltage_requirements.len() != lights {
+        let joltages = groups(input, line, '{', '}')?[0];
+        let expected = format!("{lights} joltages");
+        return Err(ParseError::at(input, joltages, expected));
+    }
+    Ok(machine)
 }
 
-fn parse_light_diagram(input: &str) -> LightDiagram {
-    parse_with_delimiters('[', ']', input, |lights| {
-        LightDiagram(
-            lights
-                .chars()
-                .map(|l| match l {
-                    '.' => false,
-                    '#' => true,
-                    _ => panic!("Light diagram cannot contain {l}"),
-                })
-                .collect::<Vec<_>>(),
-        )
-    })
-}
-
-fn parse_button_wiring_schematic(input: &str) -> Button {
-    parse_with_delimiters('(', ')', input, |content| {
-        content.split(',').map(|s| s.parse().unwrap()).collect()
-    })
-}
-
-fn parse_joltage_requirements(input: &str) -> Vec<i32> {
-    parse_with_delimiters('{', '}', input, |content| {
-        content.split(',').map(|s| s.parse().unwrap()).collect()
-    })
-}
-
-pub fn part1(machines: &Vec<Machine>) -> i32 {
+pub fn part1(machines: &Vec<Machine>) -> Result<i32, SolveError> {
     machines.iter().map(start_machine).sum()
 }
 
-fn start_machine(machine: &Machine) -> i32 {
-    for presses in 0..machine.light_diagram.0.len() {
+fn start_machine(machine: &Machine) -> Result<i32, SolveError> {
+    // Pressing a button twice undoes it, and the buttons of a shortest
+    // solution toggle independent sets of lights, so no more than one press
+    // per light is ever needed.
+    for presses in 0..=machine.light_diagram.0.len() {
         if machine
             .button_wiring_schematics
             .iter()
             .combinations(presses)
             .any(|buttons| buttons_turn_on_machine(buttons, &machine.light_diagram))
         {
-            return presses as i32;
+            return Ok(presses as i32);
         }
     }
-    panic!("No solution found for machine {machine:?}");
+    Err(SolveError::NoSolution(format!(
+        "no buttons turn on the lights of machine {machine:?}"
+    )))
 }
 
 fn buttons_turn_on_machine(buttons: Vec<&Button>, light_diagram: &LightDiagram) -> bool {
@@ -112,21 +114,27 @@ fn press_button_for_lights(lights: &mut Vec<bool>, button: &Button) {
     }
 }
 
-pub fn part2(machines: Vec<Machine>) -> i32 {
-    let counter = Arc::new(AtomicU32::new(0));
+pub fn part2(machines: Vec<Machine>) -> Result<i32, SolveError> {
     let handles: Vec<_> = machines
         .into_iter()
         .map(|machine| {
-            let counter = Arc::clone(&counter);
             thread::spawn(move || {
-                let result = configure_machine(&machine);
-                let number = counter.fetch_add(1, Ordering::Relaxed);
-                println!("Result for machine {number}: {machine:?} is {result:?}.");
-                result.expect(&format!("Machine {machine:?} should have a solution."))
+                configure_machine(&machine).ok_or_else(|| {
+                    SolveError::NoSolution(format!(
+                        "no buttons reach the joltages of machine {machine:?}"
+                    ))
+                })
             })
         })
         .collect();
-    handles.into_iter().map(|h| h.join().unwrap()).sum()
+    handles
+        .into_iter()
+        .map(|handle| {
+            handle
+                .join()
+                .unwrap_or_else(|panic| panic::resume_unwind(panic))
+        })
+        .sum()
 }
 
 struct RunningMachine {
@@ -287,6 +295,66 @@ fn unpress_button(machine: &mut RunningMachine, button: &Button) {
     press_button_n_times(machine, button, -1);
 }
 
+/// Machines are built from random button presses, so both the light diagram
+/// and the joltage requirements are always reachable. The lights come from
+/// fewer presses than there are lights, which is all `start_machine` tries.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let lights = rng.range(3..=7);
+            let buttons: Vec<Button> = (0..rng.range(2..=lights + 1))
+                .map(|_| {
+                    let mut button: Vec<_> = (0..lights).filter(|_| rng.chance(0.4)).collect();
+                    if button.is_empty() {
+                        button.push(rng.index(lights));
+                    }
+                    button
+                })
+                .collect();
+
+            let mut light_diagram = vec![false; lights];
+            let mut order: Vec<_> = (0..buttons.len()).collect();
+            rng.shuffle(&mut order);
+            for i in &order[..rng.range(0..=buttons.len().min(lights - 1))] {
+                press_button_for_lights(&mut light_diagram, &buttons[*i]);
+            }
+
+            let mut joltage_requirements = vec![0; lights];
+            for button in &buttons {
+                let presses = rng.range(0..=4);
+                for light in button {
+                    joltage_requirements[*light] += presses;
+                }
+            }
+
+            let buttons = buttons
+                .iter()
+                .map(|button| format!("({})", button.iter().join(",")))
+                .join(" ");
+            let joltages = joltage_requirements.iter().join(",");
+            format!(
+                "{:?} {buttons} {{{joltages}}}\n",
+                LightDiagram(light_diagram)
+            )
+        })
+        .collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 10,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| Ok(part1(&handle_input(input)?)?.to_string())),
+            Part::new(2, |input, _| Ok(part2(handle_input(input)?)?.to_string())),
+        ],
+        examples: vec![Example::new(1, "7"), Example::new(2, "33")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -295,11 +363,25 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 10);
-        let machines = handle_input(&input);
-        let res = super::part1(&machines);
+        let machines = handle_input(&input).unwrap();
+        let res = super::part1(&machines).unwrap();
         assert_eq!(res, 7);
     }
 
+    #[test]
+    pub fn test_machine_starting() {
+        let input = [
+            ("[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}", Ok(2)),
+            ("[##] (0) (1) {1,1}", Ok(2)),
+            ("[###] (0) (1) (2) {1,1,1}", Ok(3)),
+            ("[#.] (1) {1,1}", Err(())),
+        ];
+        for (machine, expected) in input {
+            let machine = parse_machine(machine, machine).unwrap();
+            assert_eq!(start_machine(&machine).map_err(|_| ()), expected);
+        }
+    }
+
     #[test]
     pub fn test_machine_configuring() {
         let input = [
@@ -315,7 +397,8 @@ mod tests {
                 11,
             ),
         ];
-        let machines = input.map(|(machine, expected)| (parse_machine(machine), expected));
+        let machines =
+            input.map(|(machine, expected)| (parse_machine(machine, machine).unwrap(), expected));
         for (machine, expected) in machines {
             let res = configure_machine(&machine);
             assert_eq!(res, Some(expected));
@@ -325,8 +408,8 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 10);
-        let machines = handle_input(&input);
-        let res = super::part2(machines);
+        let machines = handle_input(&input).unwrap();
+        let res = super::part2(machines).unwrap();
         assert_eq!(res, 33);
     }
 }
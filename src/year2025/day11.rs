@@ -1,31 +1,35 @@
+use crate::parsers::ParseError;
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
 use itertools::Itertools;
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::HashMap;
-use std::fmt::Debug;
 
 type Code<'a> = &'a str;
 
-pub fn handle_input(input: &'_ str) -> HashMap<Code<'_>, Vec<Code<'_>>> {
+pub fn handle_input(input: &'_ str) -> Result<HashMap<Code<'_>, Vec<Code<'_>>>, ParseError> {
     let mut map = HashMap::new();
     let lines = input.lines();
     for line in lines {
-        let (code, outputs) = parse_outputs(line);
+        let (code, outputs) = parse_outputs(input, line)?;
         map.insert(code, outputs);
     }
-    map
+    Ok(map)
 }
 
-fn parse_outputs(line: &'_ str) -> (Code<'_>, Vec<Code<'_>>) {
-    let (code, outputs) = line.split_once(":").unwrap();
+fn parse_outputs<'a>(input: &str, line: &'a str) -> Result<(Code<'a>, Vec<Code<'a>>), ParseError> {
+    let (code, outputs) = line
+        .split_once(":")
+        .ok_or_else(|| ParseError::at(input, line, "a device like \"aaa: bbb ccc\""))?;
     let outputs = outputs.split_whitespace().collect();
-    (code, outputs)
+    Ok((code, outputs))
 }
 
 pub fn part1(devices: &HashMap<Code, Vec<Code>>) -> u32 {
     find_paths(devices, "you", "out", 1, 0, |a, b| a + b, |_, v| v)
 }
 
-fn find_paths<'a, T: Debug + Clone>(
+fn find_paths<'a, T: Clone>(
     devices: &HashMap<Code, Vec<Code>>,
     start: Code,
     end: Code,
@@ -50,7 +54,6 @@ fn find_paths<'a, T: Debug + Clone>(
             .fold_options(default.clone(), |a, b| fold_func(a, b));
         if let Some(val) = val {
             if code == start {
-                println!("{val:?} paths found from {start} to {end}");
                 return val;
             }
             let val = adapt(code, val);
@@ -58,7 +61,6 @@ fn find_paths<'a, T: Debug + Clone>(
             to_check.append(&mut reverse.get(code).unwrap_or(&Vec::new()).clone())
         }
     }
-    println!("No paths found from {start} to {end}");
     default
 }
 
@@ -112,6 +114,70 @@ fn check_dac_or_fft(code: Code, (c, dac, fft): CountStatus) -> CountStatus {
     }
 }
 
+/// Devices form a chain from "svr" to "out" that passes "you", "dac" and
+/// "fft", plus a few random shortcuts forward along the chain. Every path is
+/// fixed by the shortcuts it takes, so limiting their number keeps the path
+/// counts within range.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let special = ["svr", "you", "dac", "fft", "out"];
+    let mut names = Vec::new();
+    while names.len() < size {
+        let name: String = (0..3)
+            .map(|_| *rng.choose(b"abcdefghijklmnopqrstuvwxyz") as char)
+            .collect();
+        if !special.contains(&name.as_str()) && !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    for name in ["you", "dac", "fft"] {
+        let position = rng.range(0..=names.len());
+        names.insert(position, name.to_string());
+    }
+    names.insert(0, "svr".to_string());
+    names.push("out".to_string());
+
+    let mut outputs: Vec<Vec<usize>> = (1..names.len()).map(|i| vec![i]).collect();
+    for _ in 0..rng.range(0..=size.min(20)) {
+        let from = rng.index(names.len() - 1);
+        let to = rng.range(from + 1..=names.len() - 1);
+        if !outputs[from].contains(&to) {
+            outputs[from].push(to);
+        }
+    }
+
+    let mut lines: Vec<_> = outputs
+        .iter()
+        .enumerate()
+        .map(|(i, outputs)| {
+            format!(
+                "{}: {}",
+                names[i],
+                outputs.iter().map(|j| &names[*j]).join(" ")
+            )
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.join("\n") + "\n"
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 11,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| Ok(part1(&handle_input(input)?).to_string())),
+            Part::new(2, |input, _| Ok(part2(&handle_input(input)?).to_string())),
+        ],
+        examples: vec![
+            Example::new(1, "5"),
+            Example::new(2, "2").with_test_input(112),
+        ],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,7 +186,7 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 11);
-        let devices = handle_input(&input);
+        let devices = handle_input(&input).unwrap();
         let res = super::part1(&devices);
         assert_eq!(res, 5);
     }
@@ -128,7 +194,7 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 11_2);
-        let devices = handle_input(&input);
+        let devices = handle_input(&input).unwrap();
         let res = super::part2(&devices);
         assert_eq!(res, 2);
     }
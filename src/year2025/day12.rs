@@ -1,78 +1,273 @@
-use std::str::Lines;
+use crate::grid::{Grid, Pos};
+use crate::parsers::{
+    ParseError, Section, char_at, parse_all, sections, separated, token, try_map, unsigned,
+};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution, SolveError};
+use crate::scan;
+use itertools::process_results;
+use std::array;
 
-type Present = [[bool; 3]; 3];
+type Present = Grid<bool>;
+
+/// The cells of a present in one orientation, relative to its first cell in
+/// reading order.
+type Shape = Vec<(isize, isize)>;
+
+/// How many placements the packing search tries before giving up on a region.
+const MAX_STEPS: usize = 100_000;
 
 type Region = ((usize, usize), [u32; 6]);
 
-pub fn handle_input(input: &str) -> ([Present; 6], Vec<Region>) {
-    let mut lines = input.lines();
-    let mut presents = Vec::new();
-    for i in 0..6 {
-        presents.push(parse_present(&mut lines, i));
+pub fn handle_input(input: &str) -> Result<([Present; 6], Vec<Region>), ParseError> {
+    let mut sections = sections(input);
+    let mut presents: [Present; 6] = array::from_fn(|_| Grid::new(3, 3, false));
+    for (i, present) in presents.iter_mut().enumerate() {
+        let section = sections.next_section(format!("present {i}"))?;
+        *present = parse_present(input, section, i)
+            .map_err(|error| error.context(format!("present {i}")))?;
     }
-    let presents: [Present; 6] = presents.try_into().unwrap();
 
-    let regions = lines.map(parse_region).collect();
-    (presents, regions)
+    let regions = match sections.next() {
+        Some(regions) => regions
+            .lines()
+            .map(|line| parse_region(input, line))
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+    sections.finish()?;
+    Ok((presents, regions))
 }
 
-fn parse_present(lines: &mut Lines, i: usize) -> Present {
-    let mut present = [[false; 3]; 3];
-    assert_eq!(lines.next().unwrap(), format!("{i}:"));
+fn parse_present(input: &str, section: Section, i: usize) -> Result<Present, ParseError> {
+    let mut present = Grid::new(3, 3, false);
+    let mut rows = section.header(input, &format!("{i}:"))?;
     for y in 0..3 {
-        let line = lines.next().unwrap();
-        let mut line = line.chars();
+        let line = rows
+            .next()
+            .ok_or_else(|| ParseError::at(input, section.end(), format!("row {y}")))?;
+        let mut chars = line.char_indices();
         for x in 0..3 {
-            let val = match line.next() {
-                Some('.') => false,
-                Some('#') => true,
-                Some(c) => panic!("Invalid character '{c}' at position ({x},{y}) in present {i}"),
-                None => panic!("Missing character at position ({x},{y}) in present {i}"),
+            let val = match chars.next() {
+                Some((_, '.')) => false,
+                Some((_, '#')) => true,
+                Some((j, _)) => return Err(ParseError::at(input, char_at(line, j), "'.' or '#'")),
+                None => return Err(ParseError::at(input, &line[line.len()..], "'.' or '#'")),
             };
-            present[x][y] = val;
+            present.set(x, y, val);
         }
+        if let Some((j, _)) = chars.next() {
+            return Err(ParseError::at(input, &line[j..], "the end of the row"));
+        }
+    }
+    if let Some(line) = rows.next() {
+        return Err(ParseError::at(input, line, "an empty line"));
     }
-    assert_eq!(lines.next().unwrap(), "");
-    present
+    Ok(present)
 }
 
-fn parse_region(line: &str) -> Region {
-    let (dims, presents) = line.split_once(':').unwrap();
-    let (x, y) = dims.split_once('x').unwrap();
-    let (x, y) = (x.parse().unwrap(), y.parse().unwrap());
-    let presents = presents
-        .split_whitespace()
-        .map(|x| x.parse().unwrap())
-        .collect::<Vec<u32>>()
-        .try_into()
-        .unwrap();
-    ((x, y), presents)
+fn parse_region(input: &str, line: &str) -> Result<Region, ParseError> {
+    let (x, y, presents) = scan!(input, line, "{}x{}: {}", usize, usize, &str)?;
+    let counts = try_map(separated(unsigned(), token(" ")), |counts: Vec<u32>| {
+        <[u32; 6]>::try_from(counts).map_err(|_| "6 numbers of presents")
+    });
+    Ok(((x, y), parse_all(input, presents, counts)?))
 }
 
-pub fn part1(presents: &[Present; 6], regions: &Vec<Region>) -> usize {
-    regions.iter().filter(|region| presents_fit(region, presents)).count()
+pub fn part1(presents: &[Present; 6], regions: &Vec<Region>) -> Result<usize, SolveError> {
+    let shapes = presents.each_ref().map(shapes);
+    let fits = regions
+        .iter()
+        .map(|region| presents_fit(region, presents, &shapes));
+    process_results(fits, |fits| fits.filter(|fits| *fits).count())
 }
 
-fn presents_fit(region: &Region, presents: &[Present; 6]) -> bool {
+/// Every distinct orientation of a present.
+fn shapes(present: &Present) -> Vec<Shape> {
+    let orientations = present.symmetries();
+    orientations
+        .iter()
+        .map(|orientation| {
+            let cells: Vec<_> = orientation
+                .enumerate()
+                .filter(|(_, filled)| **filled)
+                .map(|((x, y), _)| (x as isize, y as isize))
+                .collect();
+            let Some(&(first_x, first_y)) = cells.first() else {
+                return Vec::new();
+            };
+            cells
+                .iter()
+                .map(|(x, y)| (x - first_x, y - first_y))
+                .collect()
+        })
+        .collect()
+}
+
+fn presents_fit(
+    region: &Region,
+    presents: &[Present; 6],
+    shapes: &[Vec<Shape>; 6],
+) -> Result<bool, SolveError> {
     let ((x, y), numbers) = region;
     let total_presents: u32 = numbers.iter().sum();
-    let space_per_present: [u32; 6] = presents.map(|p| p.iter().map(|line| {
-        (if line[0] { 1 } else { 0 })
-            + if line[1] { 1 } else { 0 }
-            + if line[2] { 1 } else { 0 }
-    }).sum());
+    let space_per_present: [u32; 6] = presents
+        .each_ref()
+        .map(|present| present.iter().filter(|filled| **filled).count() as u32);
     let mut total_spaces = 0;
     for i in 0..6 {
         total_spaces += space_per_present[i] * numbers[i];
     }
 
     if (x/3)*(y/3) >= total_presents as usize {
-        return true
+        return Ok(true)
     }
     if total_spaces as usize > x * y {
-        return false
+        return Ok(false)
+    }
+    let mut packing = Packing {
+        shapes,
+        counts: *numbers,
+        slack: x * y - total_spaces as usize,
+        steps: 0,
+    };
+    packing
+        .pack(&mut Grid::new(*x, *y, false), 0)
+        .ok_or_else(|| {
+            SolveError::Unsupported(format!(
+                "the presents {numbers:?} might fit in the {x}x{y} region only by interlocking \
+                 them, and the search for a packing gave up"
+            ))
+        })
+}
+
+/// A search for a packing that takes the free cells of a region in reading
+/// order. The first free cell is either covered by the first cell of a shape
+/// or left empty, as long as enough cells stay free for the other presents.
+struct Packing<'a> {
+    shapes: &'a [Vec<Shape>; 6],
+    counts: [u32; 6],
+    /// How many more cells can be left empty.
+    slack: usize,
+    steps: usize,
+}
+
+impl Packing<'_> {
+    /// Whether the remaining presents fit in the free cells from the `from`th
+    /// cell on, `None` when the search takes too long.
+    fn pack(&mut self, region: &mut Grid<bool>, from: usize) -> Option<bool> {
+        if self.counts.iter().all(|count| *count == 0) {
+            return Some(true);
+        }
+        self.steps += 1;
+        if self.steps > MAX_STEPS {
+            return None;
+        }
+        let width = region.get_width();
+        let cells = width * region.get_height();
+        let Some(index) = (from..cells).find(|i| !region[Pos::new(i % width, i / width)]) else {
+            return Some(false);
+        };
+        let pos = Pos::new(index % width, index / width);
+
+        let shapes = self.shapes;
+        for (i, orientations) in shapes.iter().enumerate() {
+            if self.counts[i] == 0 {
+                continue;
+            }
+            for shape in orientations {
+                let Some(covered) = place(region, pos, shape) else {
+                    continue;
+                };
+                self.counts[i] -= 1;
+                let packed = self.pack(region, index + 1);
+                self.counts[i] += 1;
+                covered.iter().for_each(|cell| region[*cell] = false);
+                if packed != Some(false) {
+                    return packed;
+                }
+            }
+        }
+        if self.slack == 0 {
+            return Some(false);
+        }
+        self.slack -= 1;
+        region[pos] = true;
+        let packed = self.pack(region, index + 1);
+        region[pos] = false;
+        self.slack += 1;
+        packed
+    }
+}
+
+/// Covers the cells of `shape` with its first cell at `pos`, if they are all
+/// free.
+fn place(region: &mut Grid<bool>, pos: Pos, shape: &Shape) -> Option<Vec<Pos>> {
+    let cells: Vec<Pos> = shape
+        .iter()
+        .map(|(dx, dy)| {
+            pos.offset(*dx, *dy)
+                .filter(|cell| region.contains(*cell) && !region[*cell])
+        })
+        .collect::<Option<_>>()?;
+    cells.iter().for_each(|cell| region[*cell] = true);
+    Some(cells)
+}
+
+/// Regions either have room for every present in its own 3x3 square or have
+/// fewer spaces than the presents need, the two cases `presents_fit` decides.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut spaces = [0; 6];
+    for (i, space) in spaces.iter_mut().enumerate() {
+        input += &format!("{i}:\n");
+        for y in 0..3 {
+            for x in 0..3 {
+                let filled = (x, y) == (1, 1) || rng.chance(0.7);
+                *space += filled as usize;
+                input.push(if filled { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input.push('\n');
+    }
+
+    for _ in 0..size {
+        let (width, height) = (rng.range(3..=50), rng.range(3..=50));
+        let mut numbers = [0; 6];
+        if rng.chance(0.5) {
+            for _ in 0..rng.range(0..=(width / 3) * (height / 3)) {
+                numbers[rng.index(6)] += 1;
+            }
+        } else {
+            let mut total_space = 0;
+            while total_space <= width * height {
+                let i = rng.index(6);
+                numbers[i] += 1;
+                total_space += spaces[i];
+            }
+        }
+        input += &format!(
+            "{width}x{height}: {}\n",
+            numbers.map(|n| n.to_string()).join(" ")
+        );
+    }
+    input
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 12,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![Part::new(1, |input, _| {
+            let (presents, regions) = handle_input(input)?;
+            Ok(part1(&presents, &regions)?.to_string())
+        })],
+        examples: vec![Example::new(1, "2")],
     }
-    panic!("This one is actually hard...")
 }
 
 #[cfg(test)]
@@ -83,8 +278,28 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 12);
-        let (presents, regions) = handle_input(&input);
-        let res = super::part1(&presents, &regions);
+        let (presents, regions) = handle_input(&input).unwrap();
+        let res = super::part1(&presents, &regions).unwrap();
         assert_eq!(res, 2);
     }
+
+    fn present(rows: &str) -> Present {
+        Grid::parse_with(rows, |c| Ok::<_, &str>(c == '#')).unwrap()
+    }
+
+    #[test]
+    pub fn packs_presents() {
+        let square = present("###\n###\n###\n");
+        let corner = present("###\n#..\n...\n");
+        let presents = [&square, &corner, &square, &square, &square, &square].map(Present::clone);
+        // The corners only fit turned against each other, as AAAB/ABBB.
+        let regions = vec![((4, 2), [0, 2, 0, 0, 0, 0]), ((5, 5), [2, 0, 0, 0, 0, 0])];
+        assert_eq!(super::part1(&presents, &regions), Ok(1));
+
+        let regions = vec![((20, 20), [41, 0, 0, 0, 0, 0])];
+        assert!(matches!(
+            super::part1(&presents, &regions),
+            Err(SolveError::Unsupported(_))
+        ));
+    }
 }
@@ -1,10 +1,28 @@
-pub fn handle_input(input: &str) -> impl Iterator<Item = (u64, u64)> {
-    input.split(',').map(|range| {
-        let (begin, end) = range.split_once('-').unwrap();
-        let begin: u64 = begin.parse::<u64>().unwrap();
-        let end: u64 = end.parse::<u64>().unwrap();
-        (begin, end)
-    })
+use crate::input::stream;
+use crate::parsers::{ParseError, parse_number};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
+use itertools::{Itertools, process_results};
+use std::io::BufRead;
+
+pub fn handle_input(input: &str) -> impl Iterator<Item = Result<(u64, u64), ParseError>> {
+    input.split(',').map(|range| parse_range(input, range))
+}
+
+/// Reads the ranges one at a time, as the whole input is a single line.
+pub fn stream_input(reader: impl BufRead) -> impl Iterator<Item = Result<(u64, u64), String>> {
+    stream(reader, b',', |range| parse_range(range, range))
+}
+
+fn parse_range(input: &str, range: &str) -> Result<(u64, u64), ParseError> {
+    let range = range.trim();
+    let (begin, end) = range
+        .split_once('-')
+        .ok_or_else(|| ParseError::at(input, range, "a range like 11-22"))?;
+    Ok((
+        parse_number(input, begin, "an id")?,
+        parse_number(input, end, "an id")?,
+    ))
 }
 
 pub fn part1(ranges: impl Iterator<Item = (u64, u64)>) -> u64 {
@@ -42,24 +60,203 @@ fn is_repeated(id: u64) -> bool {
     false
 }
 
+/// Sums the invalid ids without visiting every id in the ranges. An id of
+/// `len` digits that repeats a block of `block` digits is the block times
+/// `(10^len - 1) / (10^block - 1)`, so per length these ids form an
+/// arithmetic series.
+pub fn part1_fast(ranges: impl Iterator<Item = (u64, u64)>) -> u64 {
+    ranges
+        .map(|(begin, end)| {
+            (digits(begin)..=digits(end))
+                .filter(|len| len % 2 == 0)
+                .map(|len| repeated_sum(begin, end, len, len / 2))
+                .sum::<u64>()
+        })
+        .sum()
+}
+
+pub fn part2_fast(ranges: impl Iterator<Item = (u64, u64)>) -> u64 {
+    ranges
+        .map(|(begin, end)| {
+            (digits(begin)..=digits(end))
+                .map(|len| repeated_sum_any_block(begin, end, len))
+                .sum::<u64>()
+        })
+        .sum()
+}
+
+/// Repeating a block of length `len / p` for a prime `p` covers all shorter
+/// blocks that divide it, so inclusion-exclusion over the prime factors of
+/// `len` counts every id exactly once.
+fn repeated_sum_any_block(begin: u64, end: u64, len: u32) -> u64 {
+    let primes: Vec<u32> = (2..=len)
+        .filter(|p| len.is_multiple_of(*p) && is_prime(*p))
+        .collect();
+    let mut added = 0;
+    let mut removed = 0;
+    for subset in primes.iter().powerset().skip(1) {
+        let product: u32 = subset.iter().copied().product();
+        let sum = repeated_sum(begin, end, len, len / product);
+        if subset.len() % 2 == 1 {
+            added += sum;
+        } else {
+            removed += sum;
+        }
+    }
+    added - removed
+}
+
+fn repeated_sum(begin: u64, end: u64, len: u32, block: u32) -> u64 {
+    let multiplier = (10u128.pow(len) - 1) / (10u128.pow(block) - 1);
+    let lowest_block = 10u128.pow(block - 1);
+    let highest_block = 10u128.pow(block) - 1;
+    let low = lowest_block.max((begin as u128).div_ceil(multiplier));
+    let high = highest_block.min(end as u128 / multiplier);
+    if low > high {
+        return 0;
+    }
+    (multiplier * (low + high) * (high - low + 1) / 2) as u64
+}
+
+fn digits(id: u64) -> u32 {
+    id.checked_ilog10().unwrap_or(0) + 1
+}
+
+fn is_prime(n: u32) -> bool {
+    n >= 2
+        && (2..n)
+            .take_while(|d| d * d <= n)
+            .all(|d| !n.is_multiple_of(d))
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let digits = rng.range(1..=10);
+            let begin = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let end = begin + rng.range(0..=999);
+            format!("{begin}-{end}")
+        })
+        .join(",")
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 2,
+        parse: |input| {
+            let ranges: Vec<_> = handle_input(input).collect::<Result<_, _>>()?;
+            Ok(format!("{ranges:#?}"))
+        },
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| {
+                Ok(process_results(handle_input(input), |ranges| {
+                    part1(ranges).to_string()
+                })?)
+            })
+            .streamed(|reader, _| {
+                process_results(stream_input(reader), |ranges| part1(ranges).to_string())
+            }),
+            Part::new(2, |input, _| {
+                Ok(process_results(handle_input(input), |ranges| {
+                    part2(ranges).to_string()
+                })?)
+            })
+            .streamed(|reader, _| {
+                process_results(stream_input(reader), |ranges| part2(ranges).to_string())
+            }),
+            Part::alternative(1, "fast", |input, _| {
+                Ok(process_results(handle_input(input), |ranges| {
+                    part1_fast(ranges).to_string()
+                })?)
+            }),
+            Part::alternative(2, "fast", |input, _| {
+                Ok(process_results(handle_input(input), |ranges| {
+                    part2_fast(ranges).to_string()
+                })?)
+            }),
+        ],
+        examples: vec![Example::new(1, "1227775554"), Example::new(2, "4174379265")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::input::get_test_input;
+    use crate::property::{assert_property, shrink_number, shrink_vec};
 
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 2);
-        let input = handle_input(&input);
-        let res = super::part1(input);
+        let res = process_results(handle_input(&input), |input| super::part1(input)).unwrap();
         assert_eq!(res, 1227775554);
     }
 
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 2);
-        let input = handle_input(&input);
-        let res = super::part2(input);
+        let res = process_results(handle_input(&input), |input| super::part2(input)).unwrap();
         assert_eq!(res, 4174379265);
     }
+
+    #[test]
+    pub fn fast() {
+        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1-1000000";
+        let ranges = || handle_input(input).map(Result::unwrap);
+        assert_eq!(part1_fast(ranges()), super::part1(ranges()));
+        assert_eq!(part2_fast(ranges()), super::part2(ranges()));
+    }
+
+    #[test]
+    pub fn streamed() {
+        let input = "11-22,95-115,\n998-1012,1188511880-1188511890\n";
+        let streamed: Vec<_> = stream_input(input.as_bytes()).collect();
+        let loaded: Vec<_> = handle_input(input)
+            .map(|r| r.map_err(String::from))
+            .collect();
+        assert_eq!(streamed, loaded);
+
+        let input = "11-22,95-115,\n998-x012";
+        let error = stream_input(input.as_bytes())
+            .find_map(Result::err)
+            .unwrap();
+        assert_eq!(
+            error.lines().next(),
+            Some("line 2, column 5: expected an id, found \"x012\"")
+        );
+        assert!(error.ends_with("998-x012\n  |     ^^^^"), "{error}");
+    }
+
+    #[test]
+    pub fn fast_matches_naive() {
+        assert_property(
+            "day2 fast implementations match the naive ones",
+            |rng| {
+                let ranges = rng.range(1..=5);
+                (0..ranges)
+                    .map(|_| {
+                        let digits = rng.range(1..=7);
+                        let begin = rng.range(1..=10u64.pow(digits));
+                        (begin, begin + rng.range(0..=2000))
+                    })
+                    .collect::<Vec<_>>()
+            },
+            |ranges| {
+                shrink_vec(ranges, |(begin, end)| {
+                    shrink_number(*end, *begin)
+                        .into_iter()
+                        .map(|end| (*begin, end))
+                        .collect()
+                })
+            },
+            |ranges| {
+                let ranges = || ranges.iter().copied();
+                part1_fast(ranges()) == super::part1(ranges())
+                    && part2_fast(ranges()) == super::part2(ranges())
+            },
+        );
+    }
 }
@@ -1,7 +1,36 @@
-pub fn handle_input(input: &str) -> impl Iterator<Item = Vec<u32>> {
-    input
-        .lines()
-        .map(|line| line.chars().map(|i| i.to_digit(10).unwrap()).collect())
+use crate::input::stream;
+use crate::parsers::{ParseError, char_at};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution, SolveError};
+use itertools::process_results;
+use std::io::BufRead;
+
+pub fn handle_input(input: &str) -> impl Iterator<Item = Result<Vec<u32>, ParseError>> {
+    input.lines().map(|line| parse_bank(input, line))
+}
+
+/// Reads the banks one line at a time.
+pub fn stream_input(reader: impl BufRead) -> impl Iterator<Item = Result<Vec<u32>, String>> {
+    stream(reader, b'\n', |line| parse_bank(line, line))
+}
+
+fn parse_bank(input: &str, line: &str) -> Result<Vec<u32>, ParseError> {
+    let bank: Vec<u32> = line
+        .char_indices()
+        .map(|(i, battery)| {
+            battery
+                .to_digit(10)
+                .ok_or_else(|| ParseError::at(input, char_at(line, i), "a battery digit"))
+        })
+        .collect::<Result<_, _>>()?;
+    if bank.len() < 2 {
+        return Err(ParseError::at(
+            input,
+            line,
+            "a bank of at least 2 batteries",
+        ));
+    }
+    Ok(bank)
 }
 
 pub fn part1(input: impl Iterator<Item = Vec<u32>>) -> u32 {
@@ -18,8 +47,16 @@ fn bank_joltage(bank: Vec<u32>) -> u32 {
     max_joltage
 }
 
-pub fn part2(input: impl Iterator<Item = Vec<u32>>) -> u64 {
-    input.map(bank_joltage_with_override).sum()
+pub fn part2(input: impl Iterator<Item = Vec<u32>>) -> Result<u64, SolveError> {
+    input
+        .map(|bank| match bank.len() {
+            ..12 => Err(SolveError::Unsupported(format!(
+                "a bank of {} batteries cannot turn on 12",
+                bank.len()
+            ))),
+            _ => Ok(bank_joltage_with_override(bank)),
+        })
+        .sum()
 }
 
 fn bank_joltage_with_override(bank: Vec<u32>) -> u64 {
@@ -34,24 +71,127 @@ fn bank_joltage_with_override(bank: Vec<u32>) -> u64 {
     num
 }
 
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let length = rng.range(12..=100);
+    (0..size)
+        .map(|_| {
+            let bank: String = (0..length)
+                .map(|_| char::from_digit(rng.range(1..=9), 10).unwrap())
+                .collect();
+            bank + "\n"
+        })
+        .collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 3,
+        parse: |input| {
+            let banks: Vec<_> = handle_input(input).collect::<Result<_, _>>()?;
+            Ok(format!("{banks:?}"))
+        },
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| {
+                Ok(process_results(handle_input(input), |banks| {
+                    part1(banks).to_string()
+                })?)
+            })
+            .streamed(|reader, _| {
+                process_results(stream_input(reader), |banks| part1(banks).to_string())
+            }),
+            Part::new(2, |input, _| {
+                Ok(process_results(handle_input(input), |banks| part2(banks))??.to_string())
+            })
+            .streamed(|reader, _| {
+                Ok(process_results(stream_input(reader), |banks| part2(banks))??.to_string())
+            }),
+        ],
+        examples: vec![Example::new(1, "357"), Example::new(2, "3121910778619")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::input::get_test_input;
+    use crate::property::{assert_property, shrink_number, shrink_vec};
+    use itertools::Itertools;
 
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 3);
-        let input = handle_input(&input);
-        let res = super::part1(input);
+        let res = process_results(handle_input(&input), |input| super::part1(input)).unwrap();
         assert_eq!(res, 357);
     }
 
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 3);
-        let input = handle_input(&input);
-        let res = super::part2(input);
-        assert_eq!(res, 3121910778619);
+        let res = process_results(handle_input(&input), |input| super::part2(input)).unwrap();
+        assert_eq!(res, Ok(3121910778619));
+    }
+
+    #[test]
+    pub fn short_banks_are_rejected() {
+        let input = "987654321111111\n\n8\n";
+        let error = handle_input(input).find_map(Result::err).unwrap();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.expected, "a bank of at least 2 batteries");
+        let errors: Vec<_> = stream_input(input.as_bytes())
+            .filter_map(Result::err)
+            .collect();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[1].starts_with("line 3, column 1: expected a bank of at least 2"));
+    }
+
+    #[test]
+    pub fn part2_needs_12_batteries() {
+        let input = "987654321111111\n81111119\n";
+        let banks = || handle_input(input).map(Result::unwrap);
+        assert_eq!(super::part1(banks()), 98 + 89);
+        assert_eq!(
+            super::part2(banks()),
+            Err(SolveError::Unsupported(
+                "a bank of 8 batteries cannot turn on 12".to_string()
+            ))
+        );
+    }
+
+    fn largest_joltage(bank: &[u32], batteries: usize) -> u64 {
+        bank.iter()
+            .combinations(batteries)
+            .map(|digits| digits.iter().fold(0, |num, d| 10 * num + **d as u64))
+            .max()
+            .unwrap()
+    }
+
+    #[test]
+    pub fn matches_exhaustive_search() {
+        assert_property(
+            "day3 matches an exhaustive search over the batteries",
+            |rng| {
+                let length = rng.range(12..=16);
+                (0..length).map(|_| rng.range(1..=9)).collect::<Vec<u32>>()
+            },
+            |bank| {
+                shrink_vec(bank, |d| {
+                    shrink_number(*d as u64, 1)
+                        .into_iter()
+                        .map(|d| d as u32)
+                        .collect()
+                })
+                .into_iter()
+                .filter(|bank| bank.len() >= 12)
+                .collect()
+            },
+            |bank| {
+                let part1 = super::part1([bank.clone()].into_iter());
+                let part2 = super::part2([bank.clone()].into_iter());
+                part1 as u64 == largest_joltage(bank, 2) && part2 == Ok(largest_joltage(bank, 12))
+            },
+        );
     }
 }
@@ -1,27 +1,30 @@
-use crate::grid::{Grid, read_grid};
+use crate::grid::{Grid, Pos};
+use crate::parsers::ParseError;
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
 
-pub fn handle_input(input: &str) -> Grid<bool> {
-    read_grid(input).map(|x| *x == '@')
+pub fn handle_input(input: &str) -> Result<Grid<bool>, ParseError> {
+    Grid::parse_with(input, |c| match c {
+        '@' => Ok(true),
+        '.' => Ok(false),
+        _ => Err("'@' or '.'"),
+    })
 }
 
 pub fn part1(grid: &Grid<bool>) -> usize {
-    let mut count = 0;
-    for i in 0..grid.get_height() {
-        for j in 0..grid.get_width() {
-            if grid.get(i, j).unwrap() && accessible(grid, i, j) {
-                count += 1;
-            }
-        }
-    }
-    count
+    grid.enumerate()
+        .filter(|&((x, y), roll)| *roll && accessible(grid, Pos::new(x, y)))
+        .count()
 }
 
-fn accessible(grid: &Grid<bool>, i: usize, j: usize) -> bool {
-    count_neighbours(grid, i, j) < 4
+fn accessible(grid: &Grid<bool>, pos: Pos) -> bool {
+    count_neighbours(grid, pos) < 4
 }
 
-fn count_neighbours(grid: &Grid<bool>, i: usize, j: usize) -> usize {
-    grid.neighbours(i, j).into_iter().filter(|x| *x).count()
+fn count_neighbours(grid: &Grid<bool>, pos: Pos) -> usize {
+    grid.neighbours8(pos.x, pos.y)
+        .filter(|(_, _, roll)| *roll)
+        .count()
 }
 
 pub fn part2(grid: &mut Grid<bool>) -> usize {
@@ -37,17 +40,54 @@ pub fn part2(grid: &mut Grid<bool>) -> usize {
 
 fn remove_rolls(grid: &mut Grid<bool>) -> usize {
     let mut count = 0;
-    for i in 0..grid.get_height() {
-        for j in 0..grid.get_width() {
-            if grid.get(i, j).unwrap() && accessible(grid, i, j) {
-                grid.set(i, j, false);
-                count += 1;
-            }
+    for pos in grid.positions() {
+        if grid[pos] && accessible(grid, pos) {
+            grid[pos] = false;
+            count += 1;
         }
     }
     count
 }
 
+/// The grid with the rolls that can be removed right away marked `x`.
+pub fn visualize(grid: &Grid<bool>) -> String {
+    let mut marked = grid.map(|roll| if *roll { '@' } else { '.' });
+    for ((x, y), cell) in marked.enumerate_mut() {
+        if *cell == '@' && accessible(grid, Pos::new(x, y)) {
+            *cell = 'x';
+        }
+    }
+    marked.to_string()
+}
+
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size)
+        .map(|_| {
+            let row: String = (0..size)
+                .map(|_| if rng.chance(0.6) { '@' } else { '.' })
+                .collect();
+            row + "\n"
+        })
+        .collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 4,
+        parse: |input| Ok(format!("{:?}", handle_input(input)?)),
+        generate,
+        visualize: Some(|input| Ok(visualize(&handle_input(input)?))),
+        parts: vec![
+            Part::new(1, |input, _| Ok(part1(&handle_input(input)?).to_string())),
+            Part::new(2, |input, _| {
+                Ok(part2(&mut handle_input(input)?).to_string())
+            }),
+        ],
+        examples: vec![Example::new(1, "13"), Example::new(2, "43")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -56,7 +96,7 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 4);
-        let input = handle_input(&input);
+        let input = handle_input(&input).unwrap();
         let res = super::part1(&input);
         assert_eq!(res, 13);
     }
@@ -64,7 +104,7 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 4);
-        let mut input = handle_input(&input);
+        let mut input = handle_input(&input).unwrap();
         let res = super::part2(&mut input);
         assert_eq!(res, 43);
     }
@@ -1,17 +1,45 @@
+use crate::parsers::{ParseError, parse_number, sections};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
+use crate::scan;
 use std::cmp::{max, min};
 
 type Id = u64;
 
-pub fn handle_input(input: &str) -> (Vec<(Id, Id)>, Vec<Id>) {
-    let mut lines = input.lines();
-    let ranges = lines.by_ref().map_while(read_range).collect();
-    let ids = lines.map(|x| x.parse::<Id>().unwrap()).collect();
-    (ranges, ids)
+type Range = (Id, Id);
+
+pub fn handle_input(input: &str) -> Result<(Vec<Range>, Vec<Id>), ParseError> {
+    let mut sections = sections(input);
+    let ranges = sections
+        .next_section("fresh ranges")?
+        .lines()
+        .map(|range| read_range(input, range))
+        .collect::<Result<_, _>>()?;
+    let ids = match sections.next() {
+        Some(ids) => ids
+            .lines()
+            .map(|id| read_id(input, id))
+            .collect::<Result<_, _>>()?,
+        None => Vec::new(),
+    };
+    sections.finish()?;
+    Ok((ranges, ids))
+}
+
+fn read_range(input: &str, range: &str) -> Result<Range, ParseError> {
+    let (low, high) = scan!(input, range, "{}-{}", Id, Id)?;
+    if low > high {
+        return Err(ParseError::at(
+            input,
+            range,
+            "a range that ends after it starts",
+        ));
+    }
+    Ok((low, high))
 }
 
-fn read_range(range: &str) -> Option<(Id, Id)> {
-    let res = range.split_once('-');
-    res.map(|(low, high)| (low.parse().unwrap(), high.parse().unwrap()))
+fn read_id(input: &str, id: &str) -> Result<Id, ParseError> {
+    parse_number(input, id, "an id")
 }
 
 pub fn part1(ranges: &Vec<(Id, Id)>, ids: &Vec<Id>) -> usize {
@@ -61,15 +89,60 @@ fn total_range(ranges: impl IntoIterator<Item = (Id, Id)>) -> u64 {
     ranges.into_iter().map(|(low, high)| high - low + 1).sum()
 }
 
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let max_id = 10u64.pow(12);
+    let ranges: Vec<(Id, Id)> = (0..size.max(1))
+        .map(|_| {
+            let low = rng.range(1..=max_id);
+            (low, low + rng.range(0..=max_id / 1000))
+        })
+        .collect();
+    let mut input: String = ranges
+        .iter()
+        .map(|(low, high)| format!("{low}-{high}\n"))
+        .collect();
+    input += "\n";
+    for _ in 0..size {
+        let id = if rng.chance(0.5) {
+            let (low, high) = *rng.choose(&ranges);
+            rng.range(low..=high)
+        } else {
+            rng.range(1..=max_id)
+        };
+        input += &format!("{id}\n");
+    }
+    input
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 5,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| {
+                let (ranges, ids) = handle_input(input)?;
+                Ok(part1(&ranges, &ids).to_string())
+            }),
+            Part::new(2, |input, _| Ok(part2(&handle_input(input)?.0).to_string())),
+        ],
+        examples: vec![Example::new(1, "3"), Example::new(2, "14")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::input::get_test_input;
+    use crate::property::{assert_property, shrink_number, shrink_vec};
+    use std::collections::HashSet;
 
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 5);
-        let (ranges, ids) = handle_input(&input);
+        let (ranges, ids) = handle_input(&input).unwrap();
         let res = super::part1(&ranges, &ids);
         assert_eq!(res, 3);
     }
@@ -77,8 +150,37 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 5);
-        let (ranges, _ids) = handle_input(&input);
+        let (ranges, _ids) = handle_input(&input).unwrap();
         let res = super::part2(&ranges);
         assert_eq!(res, 14);
     }
+
+    #[test]
+    pub fn part2_matches_brute_force() {
+        assert_property(
+            "day5::part2 equals the size of the union of the ranges",
+            |rng| {
+                let ranges = rng.range(1..=15);
+                (0..ranges)
+                    .map(|_| {
+                        let low = rng.range(1..=50);
+                        (low, low + rng.range(0..=10))
+                    })
+                    .collect::<Vec<(Id, Id)>>()
+            },
+            |ranges| {
+                shrink_vec(ranges, |(low, high)| {
+                    shrink_number(*high, *low)
+                        .into_iter()
+                        .map(|high| (*low, high))
+                        .collect()
+                })
+            },
+            |ranges| {
+                let fresh: HashSet<Id> =
+                    ranges.iter().flat_map(|(low, high)| *low..=*high).collect();
+                super::part2(ranges) == fresh.len() as u64
+            },
+        );
+    }
 }
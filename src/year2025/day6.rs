@@ -1,39 +1,86 @@
+use crate::grid::Grid;
+use crate::parsers::{Cell, ParseError, Table, char_at, parse_number};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
+
+#[derive(Debug)]
 pub enum Op {
     Add,
     Times,
 }
 
-pub fn handle_input(input: &str) -> Vec<&str> {
-    input.lines().collect::<Vec<&str>>()
+/// The worksheet as rows of numbers, and as a table for reading the numbers
+/// column by column.
+#[derive(Debug)]
+pub struct Worksheet<'a> {
+    table: Table<'a>,
+    nums: Grid<u64>,
+    ops: Vec<Op>,
 }
 
-fn read_nums(line: &&str) -> Vec<u64> {
+pub fn handle_input(input: &str) -> Result<Worksheet<'_>, ParseError> {
+    let mut lines: Vec<&str> = input.lines().collect();
+    let last = lines
+        .pop()
+        .ok_or_else(|| ParseError::end(input, "a worksheet"))?;
+    let ops = read_ops(input, last)?;
+    let nums: Vec<_> = lines
+        .iter()
+        .map(|line| read_nums(input, line))
+        .collect::<Result<_, _>>()?;
+    if nums.is_empty() {
+        return Err(ParseError::at(input, last, "a line of numbers"));
+    }
+    if let Some(i) = nums.iter().position(|row| row.len() != ops.len()) {
+        let expected = format!("{} numbers, one per operator", ops.len());
+        return Err(ParseError::at(input, lines[i], expected));
+    }
+    let nums = Grid::from_vec(ops.len(), nums.len(), nums.concat());
+    let table = Table::new(lines);
+    if table.columns().len() != ops.len() {
+        let expected = "problems separated by blank columns";
+        return Err(ParseError::at(input, table.lines()[0], expected));
+    }
+    Ok(Worksheet { table, nums, ops })
+}
+
+fn read_nums(input: &str, line: &str) -> Result<Vec<u64>, ParseError> {
+    if let Some((i, _)) = line
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii_digit() && *c != ' ')
+    {
+        return Err(ParseError::at(
+            input,
+            char_at(line, i),
+            "a digit or a space",
+        ));
+    }
     let words = line.split_whitespace();
-    words.map(|x| x.parse::<u64>().unwrap()).collect()
+    words.map(|x| parse_number(input, x, "a number")).collect()
 }
 
-fn read_ops(line: &str) -> Vec<Op> {
-    line.split_whitespace().map(read_op).collect()
+fn read_ops(input: &str, line: &str) -> Result<Vec<Op>, ParseError> {
+    line.split_whitespace()
+        .map(|op| read_op(input, op))
+        .collect()
 }
 
-fn read_op(input: &str) -> Op {
-    match input {
-        "+" => Op::Add,
-        "*" => Op::Times,
-        _ => panic!("Invalid operator: {}", input),
+fn read_op(input: &str, op: &str) -> Result<Op, ParseError> {
+    match op {
+        "+" => Ok(Op::Add),
+        "*" => Ok(Op::Times),
+        _ => Err(ParseError::at(input, op, "an operator '+' or '*'")),
     }
 }
 
-pub fn part1(input: Vec<&str>) -> u64 {
-    let nums: Vec<_> = input[0..input.len() - 1].iter().map(read_nums).collect();
-    let ops: Vec<_> = read_ops(input[input.len() - 1]);
-    let nums = transpose(nums);
-    compute_grand_total(&nums, &ops)
+pub fn part1(worksheet: &Worksheet) -> u64 {
+    let problems = worksheet.nums.transpose();
+    compute_grand_total(problems.rows(), &worksheet.ops)
 }
 
-fn compute_grand_total(problems: &[Vec<u64>], ops: &[Op]) -> u64 {
+fn compute_grand_total<'a>(problems: impl Iterator<Item = &'a [u64]>, ops: &[Op]) -> u64 {
     let mut grand_total = 0;
-    for (problem, op) in problems.iter().zip(ops.iter()) {
+    for (problem, op) in problems.zip(ops.iter()) {
         grand_total += match op {
             Op::Add => problem.iter().sum::<u64>(),
             Op::Times => problem.iter().product(),
@@ -42,40 +89,67 @@ fn compute_grand_total(problems: &[Vec<u64>], ops: &[Op]) -> u64 {
     grand_total
 }
 
-fn transpose<T: Copy>(vec: Vec<Vec<T>>) -> Vec<Vec<T>> {
-    let mut new_vec = Vec::new();
-    for i in 0..vec[0].len() {
-        new_vec.push(vec.iter().map(|row| row[i]).collect());
-    }
-    new_vec
+pub fn part2(worksheet: &Worksheet) -> u64 {
+    let problems: Vec<Vec<u64>> = (0..worksheet.table.columns().len())
+        .map(|column| read_vertical_numbers(&worksheet.table.column(column)))
+        .collect();
+    compute_grand_total(problems.iter().map(Vec::as_slice), &worksheet.ops)
 }
 
-pub fn part2(lines: Vec<&str>) -> u64 {
-    let ops = read_ops(lines[lines.len() - 1]);
-    let bytes = lines[0..lines.len() - 1]
-        .iter()
-        .map(|s| s.bytes().collect())
-        .collect();
-    let columns: Vec<_> = transpose(bytes)
-        .iter()
-        .map(|col| str::from_utf8(col).unwrap())
-        .map(|col| col.trim().parse::<u64>())
-        .collect();
+/// The numbers written top to bottom in each character column of a problem.
+fn read_vertical_numbers(cells: &[Cell]) -> Vec<u64> {
+    let mut numbers = vec![0; cells[0].width];
+    for cell in cells {
+        for (number, c) in numbers.iter_mut().zip(cell.chars()) {
+            if let Some(digit) = c.to_digit(10) {
+                *number = *number * 10 + digit as u64;
+            }
+        }
+    }
+    numbers
+}
 
-    let mut problems = Vec::new();
-    let mut current_problem = Vec::new();
-    for col in columns {
-        match col {
-            Ok(num) => current_problem.push(num),
-            Err(_) => {
-                problems.push(current_problem);
-                current_problem = Vec::new();
+/// Every problem is as wide as its longest number, the other numbers are
+/// aligned left or right within it and problems are separated by a blank
+/// column.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let rows = rng.range(2..=4);
+    let mut lines = vec![String::new(); rows + 1];
+    for problem in 0..size {
+        let nums: Vec<String> = (0..rows)
+            .map(|_| rng.range(1..=999u32).to_string())
+            .collect();
+        let width = nums.iter().map(String::len).max().unwrap();
+        let left_aligned = rng.chance(0.5);
+        if problem > 0 {
+            lines.iter_mut().for_each(|line| line.push(' '));
+        }
+        for (line, num) in lines.iter_mut().zip(nums) {
+            if left_aligned {
+                *line += &format!("{num:<width$}");
+            } else {
+                *line += &format!("{num:>width$}");
             }
         }
+        let op = if rng.chance(0.5) { '+' } else { '*' };
+        lines[rows] += &format!("{op:<width$}");
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 6,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| Ok(part1(&handle_input(input)?).to_string())),
+            Part::new(2, |input, _| Ok(part2(&handle_input(input)?).to_string())),
+        ],
+        examples: vec![Example::new(1, "4277556"), Example::new(2, "3263827")],
     }
-    problems.push(current_problem);
-    
-    compute_grand_total(&problems, &ops)
 }
 
 #[cfg(test)]
@@ -86,16 +160,16 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 6);
-        let lines = handle_input(&input);
-        let res = super::part1(lines);
+        let worksheet = handle_input(&input).unwrap();
+        let res = super::part1(&worksheet);
         assert_eq!(res, 4277556);
     }
 
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 6);
-        let lines = handle_input(&input);
-        let res = super::part2(lines);
+        let worksheet = handle_input(&input).unwrap();
+        let res = super::part2(&worksheet);
         assert_eq!(res, 3263827);
     }
 }
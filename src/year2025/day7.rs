@@ -1,17 +1,49 @@
-pub fn handle_input(input: &str) -> (usize, Vec<Vec<bool>>) {
-    let start = input.bytes().position(|byte| byte == b'S').unwrap();
-    let splitters = input.lines().map(|line| {
-        line.bytes().map(|b| is_splitter(b)).collect()
-    }).collect();
-    (start, splitters)
+use crate::parsers::{ParseError, char_at};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution};
+
+pub fn handle_input(input: &str) -> Result<(usize, Vec<Vec<bool>>), ParseError> {
+    let first_line = input
+        .lines()
+        .next()
+        .ok_or_else(|| ParseError::end(input, "a manifold"))?;
+    let start = first_line
+        .bytes()
+        .position(|byte| byte == b'S')
+        .ok_or_else(|| ParseError::at(input, first_line, "a first line with the start 'S'"))?;
+    let splitters: Vec<Vec<bool>> = input
+        .lines()
+        .map(|line| {
+            line.char_indices()
+                .map(|(i, _)| is_splitter(input, char_at(line, i)))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    let mut lines = input.lines().zip(&splitters);
+    if let Some((line, _)) = lines.find(|(_, l)| l.len() != first_line.len()) {
+        let expected = format!("a line of length {}", first_line.len());
+        return Err(ParseError::at(input, line, expected));
+    }
+    // A beam split at the edge would leave the manifold.
+    for (line, row) in input.lines().zip(&splitters) {
+        let last = line.char_indices().next_back().map_or(0, |(i, _)| i);
+        let edge = match (row.first(), row.last()) {
+            (Some(true), _) => 0,
+            (_, Some(true)) => last,
+            _ => continue,
+        };
+        let expected = "'.' or 'S' at the edge of the manifold";
+        return Err(ParseError::at(input, char_at(line, edge), expected));
+    }
+    Ok((start, splitters))
 }
 
-fn is_splitter(byte: u8) -> bool {
-    match byte {
-        b'^' => true,
-        b'.' => false,
-        b'S' => false,
-        _ => panic!("Invalid byte: {}", byte),
+fn is_splitter(input: &str, tile: &str) -> Result<bool, ParseError> {
+    match tile {
+        "^" => Ok(true),
+        "." => Ok(false),
+        "S" => Ok(false),
+        _ => Err(ParseError::at(input, tile, "'^', '.' or 'S'")),
     }
 }
 
@@ -68,6 +100,74 @@ fn quantum_split_and_count(splitters: Vec<bool>, tachyon_beams: &mut Vec<u64>) -
     splits
 }
 
+/// The manifold with the paths of the beams drawn as `|`.
+pub fn visualize(start: usize, splitters: &[Vec<bool>]) -> String {
+    let mut tachyon_beams = vec![false; splitters[0].len()];
+    tachyon_beams[start] = true;
+    let mut lines = Vec::new();
+    for (y, layer) in splitters.iter().enumerate() {
+        let line: String = layer
+            .iter()
+            .zip(&tachyon_beams)
+            .enumerate()
+            .map(|(x, (splitter, beam))| match (splitter, beam) {
+                (true, _) => '^',
+                (false, true) if y == 0 && x == start => 'S',
+                (false, true) => '|',
+                (false, false) => '.',
+            })
+            .collect();
+        lines.push(line);
+        split_and_count(layer.clone(), &mut tachyon_beams);
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Splitters are never placed in the outer columns, so a split beam always
+/// stays on the manifold.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = 2 * size + 1;
+    let mut lines = vec![format!("{:.<width$}", format!("{:.>1$}", 'S', size + 1))];
+    for row in 1..2 * size {
+        let line: String = (0..width)
+            .map(|x| {
+                let inside = x > 0 && x < width - 1;
+                if row % 2 == 0 && inside && rng.chance(0.3) {
+                    '^'
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        lines.push(line);
+    }
+    lines.join("\n") + "\n"
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 7,
+        parse: |input| Ok(format!("{:?}", handle_input(input)?)),
+        generate,
+        visualize: Some(|input| {
+            let (start, splitters) = handle_input(input)?;
+            Ok(visualize(start, &splitters))
+        }),
+        parts: vec![
+            Part::new(1, |input, _| {
+                let (start, splitters) = handle_input(input)?;
+                Ok(part1(start, splitters).to_string())
+            }),
+            Part::new(2, |input, _| {
+                let (start, splitters) = handle_input(input)?;
+                Ok(part2(start, splitters).to_string())
+            }),
+        ],
+        examples: vec![Example::new(1, "21"), Example::new(2, "40")],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::input::get_test_input;
@@ -76,7 +176,7 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 7);
-        let (start, splitters) = handle_input(&input);
+        let (start, splitters) = handle_input(&input).unwrap();
         let res = super::part1(start, splitters);
         assert_eq!(res, 21);
     }
@@ -84,8 +184,16 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 7);
-        let (start, splitters) = handle_input(&input);
+        let (start, splitters) = handle_input(&input).unwrap();
         let res = super::part2(start, splitters);
         assert_eq!(res, 40);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn edge_splitters_are_rejected() {
+        let error = handle_input("..S..\n.....\n....^\n").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 5));
+        assert!(handle_input("..S..\n^....\n").is_err());
+        assert!(handle_input("..S..\n.^.^.\n").is_ok());
+    }
+}
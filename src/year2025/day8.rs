@@ -1,22 +1,22 @@
+use crate::parsers::{ParseError, PuzzleParse};
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution, SolveError};
 use std::cmp::{max, min};
 use itertools::Itertools;
 
+#[derive(Debug, PuzzleParse)]
+#[puzzle(separator = ",")]
 pub struct Position {
     x: i64,
     y: i64,
     z: i64,
 }
 
-pub fn handle_input(input: &str) -> Vec<Position> {
-    input.lines().map(read_position).collect()
-}
-
-fn read_position(line: &str) -> Position {
-    let mut nums = line.split(',');
-    let x = nums.next().unwrap().parse().unwrap();
-    let y = nums.next().unwrap().parse().unwrap();
-    let z = nums.next().unwrap().parse().unwrap();
-    Position { x, y, z }
+pub fn handle_input(input: &str) -> Result<Vec<Position>, ParseError> {
+    input
+        .lines()
+        .map(|line| Position::read(input, line))
+        .collect()
 }
 
 pub fn part1(junction_boxes: Vec<Position>, connections: i32) -> i32 {
@@ -77,7 +77,7 @@ fn dist(p1: &Position, p2: &Position) -> f64 {
     f64::sqrt(((p1.x - p2.x).pow(2) + (p1.y - p2.y).pow(2) + (p1.z - p2.z).pow(2)) as f64)
 }
 
-pub fn part2(junction_boxes: Vec<Position>) -> i64 {
+pub fn part2(junction_boxes: Vec<Position>) -> Result<i64, SolveError> {
     let pairs = pairs_by_distance(&junction_boxes);
     let mut circuits = Vec::from_iter(0..junction_boxes.len());
     let mut res = None;
@@ -88,7 +88,9 @@ pub fn part2(junction_boxes: Vec<Position>) -> i64 {
             break;
         }
     }
-    res.unwrap_or_else(|| panic!())
+    res.ok_or_else(|| {
+        SolveError::NoSolution("the junction boxes never form a single circuit".to_string())
+    })
 }
 
 fn all_connected(circuits: &mut Vec<usize>) -> bool {
@@ -107,6 +109,36 @@ fn set_circuit(circuits: &mut Vec<usize>, i: &usize) {
     }
 }
 
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(3))
+        .map(|_| {
+            let [x, y, z] = [0; 3].map(|_| rng.range(0..=99_999));
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 8,
+        parse: |input| Ok(format!("{:#?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, params| {
+                let connections = params.get("connections", 1000);
+                Ok(part1(handle_input(input)?, connections).to_string())
+            }),
+            Part::new(2, |input, _| Ok(part2(handle_input(input)?)?.to_string())),
+        ],
+        examples: vec![
+            Example::new(1, "40").with_param("connections", "10"),
+            Example::new(2, "25272"),
+        ],
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,7 +167,7 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 8);
-        let junction_boxes = handle_input(&input);
+        let junction_boxes = handle_input(&input).unwrap();
         let res = super::part1(junction_boxes, 10);
         assert_eq!(res, 40);
     }
@@ -143,8 +175,8 @@ mod tests {
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 8);
-        let junction_boxes = handle_input(&input);
-        let res = super::part2(junction_boxes);
+        let junction_boxes = handle_input(&input).unwrap();
+        let res = super::part2(junction_boxes).unwrap();
         assert_eq!(res, 25272);
     }
 }
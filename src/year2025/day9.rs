@@ -1,12 +1,14 @@
-use crate::grid::Grid;
+use crate::grid::{Direction, Grid, Pos};
+use crate::parsers::ParseError;
+use crate::random::Rng;
+use crate::runner::{Example, Part, Solution, SolveError};
+use crate::scan;
 use crate::year2025::day9::Tile::{Green, Red, White};
 use itertools::Itertools;
 use std::cmp::{max, min, PartialEq, Reverse};
 use std::collections::VecDeque;
 use std::iter::repeat;
 
-pub type Position = (usize, usize);
-
 #[derive(Copy, Clone, Eq, PartialEq)]
 enum Tile {
     Red,
@@ -14,21 +16,29 @@ enum Tile {
     White,
 }
 
-pub fn handle_input(input: &str) -> Vec<Position> {
-    input.lines().map(read_position).collect()
+pub fn handle_input(input: &str) -> Result<Vec<Pos>, ParseError> {
+    input
+        .lines()
+        .map(|line| read_position(input, line))
+        .collect()
 }
 
-fn read_position(line: &str) -> Position {
-    let (x, y) = line.split_once(',').unwrap();
-    (x.parse().unwrap(), y.parse().unwrap())
+fn read_position(input: &str, line: &str) -> Result<Pos, ParseError> {
+    let (x, y) = scan!(input, line, "{},{}", usize, usize)?;
+    Ok(Pos::new(x, y))
 }
 
-pub fn part1(red_tiles: &Vec<Position>) -> usize {
-    let (_corners, area) = rectangles_by_area(red_tiles)[0];
-    area
+pub fn part1(red_tiles: &Vec<Pos>) -> Result<usize, SolveError> {
+    let (_corners, area) = rectangles_by_area(red_tiles)
+        .first()
+        .copied()
+        .ok_or_else(|| {
+            SolveError::Unsupported("a rectangle needs two red tiles as corners".to_string())
+        })?;
+    Ok(area)
 }
 
-fn rectangles_by_area(red_tiles: &Vec<Position>) -> Vec<((Position, Position), usize)> {
+fn rectangles_by_area(red_tiles: &Vec<Pos>) -> Vec<((Pos, Pos), usize)> {
     let mut pairs: Vec<_> = red_tiles
         .iter()
         .tuple_combinations::<(_, _)>()
@@ -38,114 +48,96 @@ fn rectangles_by_area(red_tiles: &Vec<Position>) -> Vec<((Position, Position), u
     pairs
 }
 
-fn rectangle_area((x1, y1): &Position, (x2, y2): &Position) -> usize {
-    let x_dist = x1.abs_diff(*x2) + 1;
-    let y_dist = y1.abs_diff(*y2) + 1;
+fn rectangle_area(p1: &Pos, p2: &Pos) -> usize {
+    let x_dist = p1.x.abs_diff(p2.x) + 1;
+    let y_dist = p1.y.abs_diff(p2.y) + 1;
     x_dist * y_dist
 }
 
-pub fn part2(red_tiles: &Vec<Position>) -> usize {
-    let green_tiles = find_green_tiles(red_tiles);
+pub fn part2(red_tiles: &Vec<Pos>) -> Result<usize, SolveError> {
+    let green_tiles = find_green_tiles(red_tiles)?;
 
     let rectangles = rectangles_by_area(red_tiles);
-    let mut counter: u64 = 0;
     rectangles
         .iter()
-        .filter(|((p1, p2), area)| {
-            counter += 1;
-            if counter % 1_000 == 0 {
-                println!("Counter: {counter}");
-                println!("Area: {area}");
-            }
-            no_white_on_rectangle_edges(&green_tiles, *p1, *p2)
-        })
+        .find(|((p1, p2), _)| no_white_on_rectangle_edges(&green_tiles, *p1, *p2))
         .map(|((_, _), area)| *area)
-        .next()
-        .expect("No rectangles completely green")
+        .ok_or_else(|| {
+            SolveError::NoSolution(
+                "no rectangle between red tiles is completely red or green".to_string(),
+            )
+        })
 }
 
-fn find_green_tiles(red_tiles: &Vec<Position>) -> Grid<Tile> {
-    let width = red_tiles.iter().map(|(x, _)| *x).max().unwrap() + 2;
-    let height = red_tiles.iter().map(|(_, y)| *y).max().unwrap() + 2;
-    let mut tiles = colour_lines_red(red_tiles, width, height);
+fn find_green_tiles(red_tiles: &Vec<Pos>) -> Result<Grid<Tile>, SolveError> {
+    let (Some(x_max), Some(y_max)) = (
+        red_tiles.iter().map(|pos| pos.x).max(),
+        red_tiles.iter().map(|pos| pos.y).max(),
+    ) else {
+        return Err(SolveError::Unsupported(
+            "there are no red tiles".to_string(),
+        ));
+    };
+    let mut tiles = colour_lines_red(red_tiles, x_max + 2, y_max + 2)?;
     colour_white_outside_red(&mut tiles);
-    tiles
+    Ok(tiles)
 }
 
 fn colour_white_outside_red(tiles: &mut Grid<Tile>) {
-    let width = tiles.get_width();
-    let height = tiles.get_height();
     let mut queue = VecDeque::new();
-    queue.push_back((0, 0));
-    tiles.set(0, 0, Green);
-    let mut counter: u64 = 0;
-
-    while let Some((x, y)) = queue.pop_front() {
-        counter += 1;
-        if counter % 100_000_000 == 0 {
-            println!("Counter: {}", counter);
-            println!("Queue size: {}", queue.len());
-        }
-        if x > 0 {
-            add_to_queue(&mut queue, tiles, x - 1, y);
-        }
-        if y > 0 {
-            add_to_queue(&mut queue, tiles, x, y - 1);
-        }
-        if x < width - 1 {
-            add_to_queue(&mut queue, tiles, x + 1, y);
-        }
-        if y < height - 1 {
-            add_to_queue(&mut queue, tiles, x, y + 1);
+    queue.push_back(Pos::new(0, 0));
+    tiles[Pos::new(0, 0)] = Green;
+
+    while let Some(pos) = queue.pop_front() {
+        for direction in Direction::ALL {
+            if let Some(next) = pos.step(direction).filter(|next| tiles.contains(*next)) {
+                add_to_queue(&mut queue, tiles, next);
+            }
         }
     }
-    println!("Tiles coloured white: {counter}/{}", width * height);
 }
 
-fn add_to_queue(queue: &mut VecDeque<(usize, usize)>, tiles: &mut Grid<Tile>, x: usize, y: usize) {
-    if tiles.get(x, y).unwrap() == Green {
-        tiles.set(x, y, White);
-        queue.push_back((x, y));
+fn add_to_queue(queue: &mut VecDeque<Pos>, tiles: &mut Grid<Tile>, pos: Pos) {
+    if tiles[pos] == Green {
+        tiles[pos] = White;
+        queue.push_back(pos);
     }
 }
 
-fn colour_lines_red(red_tiles: &Vec<Position>, width: usize, height: usize) -> Grid<Tile> {
+fn colour_lines_red(
+    red_tiles: &Vec<Pos>,
+    width: usize,
+    height: usize,
+) -> Result<Grid<Tile>, SolveError> {
     let mut on_lines = Grid::new(width, height, Green);
-    let mut points = red_tiles.iter();
-    let mut old_point = points.next().unwrap();
-    for point in points {
-        colour_line_red(&mut on_lines, old_point, point);
-        old_point = point;
+    for (p1, p2) in red_tiles.iter().circular_tuple_windows() {
+        colour_line_red(&mut on_lines, p1, p2)?;
     }
-    colour_line_red(&mut on_lines, old_point, &red_tiles[0]);
-    on_lines
+    Ok(on_lines)
 }
 
-fn colour_line_red(grid: &mut Grid<Tile>, (x1, y1): &Position, (x2, y2): &Position) {
-    if x1 == x2 && y1 != y2 {
-        let x = *x1;
-        let y_min = *min(y1, y2);
-        let y_max = *max(y1, y2);
-        for y in y_min..=y_max {
-            grid.set(x, y, Red);
+fn colour_line_red(grid: &mut Grid<Tile>, p1: &Pos, p2: &Pos) -> Result<(), SolveError> {
+    if p1.x == p2.x && p1.y != p2.y {
+        for y in min(p1.y, p2.y)..=max(p1.y, p2.y) {
+            grid[Pos::new(p1.x, y)] = Red;
         }
-    } else if x1 != x2 && y1 == y2 {
-        let y = *y1;
-        let x_min = *min(x1, x2);
-        let x_max = *max(x1, x2);
-        for x in x_min..=x_max {
-            grid.set(x, y, Red);
+    } else if p1.x != p2.x && p1.y == p2.y {
+        for x in min(p1.x, p2.x)..=max(p1.x, p2.x) {
+            grid[Pos::new(x, p1.y)] = Red;
         }
     } else {
-        panic!("The points ({x1}, {y1}) and ({x2}, {y2}) do not form a line");
+        return Err(SolveError::Unsupported(format!(
+            "the red tiles {p1} and {p2} are not on a horizontal or vertical line"
+        )));
     }
+    Ok(())
 }
 
-fn no_white_on_rectangle_edges(tiles: &Grid<Tile>, (x1, y1): Position, (x2, y2): Position) -> bool {
-    let x_min = min(x1, x2);
-    let x_max = max(x1, x2);
-    let y_min = min(y1, y2);
-    let y_max = max(y1, y2);
+fn no_white_on_rectangle_edges(tiles: &Grid<Tile>, p1: Pos, p2: Pos) -> bool {
+    let x_min = min(p1.x, p2.x);
+    let x_max = max(p1.x, p2.x);
+    let y_min = min(p1.y, p2.y);
+    let y_max = max(p1.y, p2.y);
 
     let left_edge = repeat(x_min).zip(y_min..=y_max);
     let right_edge = repeat(x_max).zip(y_min..=y_max);
@@ -156,7 +148,46 @@ fn no_white_on_rectangle_edges(tiles: &Grid<Tile>, (x1, y1): Position, (x2, y2):
         .chain(right_edge)
         .chain(top_edge)
         .chain(bottom_edge)
-        .all(|(x, y)| tiles.get(x, y).unwrap() != White)
+        .all(|(x, y)| tiles[Pos::new(x, y)] != White)
+}
+
+/// Generates a skyline: `size` blocks of random width and height standing on a
+/// common baseline. Its outline is a loop of horizontal and vertical lines that
+/// never touches the top or left edge, which the flood fill in part 2 needs.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let max_height = 10 * size;
+    let baseline = max_height + rng.range(1..=10);
+    let mut x = rng.range(1..=10);
+    let mut height = 0;
+    let mut corners = vec![(x, baseline)];
+    for _ in 0..size {
+        let mut new_height = height;
+        while new_height == height {
+            new_height = rng.range(1..=max_height);
+        }
+        height = new_height;
+        corners.push((x, height));
+        x += rng.range(1..=10);
+        corners.push((x, height));
+    }
+    corners.push((x, baseline));
+    corners.iter().map(|(x, y)| format!("{x},{y}\n")).collect()
+}
+
+pub fn solution() -> Solution {
+    Solution {
+        year: 2025,
+        day: 9,
+        parse: |input| Ok(format!("{:?}", handle_input(input)?)),
+        generate,
+        visualize: None,
+        parts: vec![
+            Part::new(1, |input, _| Ok(part1(&handle_input(input)?)?.to_string())),
+            Part::new(2, |input, _| Ok(part2(&handle_input(input)?)?.to_string())),
+        ],
+        examples: vec![Example::new(1, "50"), Example::new(2, "24")],
+    }
 }
 
 #[cfg(test)]
@@ -167,16 +198,44 @@ mod tests {
     #[test]
     pub fn part1() {
         let input = get_test_input(2025, 9);
-        let input = handle_input(&input);
-        let res = super::part1(&input);
+        let input = handle_input(&input).unwrap();
+        let res = super::part1(&input).unwrap();
         assert_eq!(res, 50);
     }
 
     #[test]
     pub fn part2() {
         let input = get_test_input(2025, 9);
-        let input = handle_input(&input);
-        let res = super::part2(&input);
+        let input = handle_input(&input).unwrap();
+        let res = super::part2(&input).unwrap();
         assert_eq!(res, 24);
     }
+
+    #[test]
+    pub fn tiles_off_a_line_are_unsupported() {
+        let red_tiles = vec![
+            Pos::new(1, 1),
+            Pos::new(3, 1),
+            Pos::new(4, 3),
+            Pos::new(1, 3),
+        ];
+        assert!(matches!(
+            super::part2(&red_tiles),
+            Err(SolveError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    pub fn too_few_tiles_are_unsupported() {
+        for red_tiles in [vec![], vec![Pos::new(2, 3)]] {
+            assert!(matches!(
+                super::part1(&red_tiles),
+                Err(SolveError::Unsupported(_))
+            ));
+            assert!(matches!(
+                super::part2(&red_tiles),
+                Err(SolveError::Unsupported(_))
+            ));
+        }
+    }
 }